@@ -1,4 +1,5 @@
 use std::any::Any;
+pub use token::Span;
 use token::TokenType;
 
 mod util;
@@ -7,6 +8,43 @@ mod util;
 pub struct Identifier {
     pub token: TokenType, // token::IDENT
     pub value: String,
+    pub span:  Span,
+}
+
+// what a value is taken apart into by let, const and parameters
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Ident(Identifier),
+    Literal(Expression), // 0, -1.5, "a" or true, only matches an equal value
+    Wildcard(Span),      // _
+
+    Array {
+        token:    TokenType, // token::LBRACKET
+        elements: Vec<Pattern>,
+        rest:     Option<Identifier>, // [a, ...rest]
+        span:     Span,
+    },
+    Hash {
+        token: TokenType,              // token::LBRACE
+        pairs: Vec<(String, Pattern)>, // {name} is ("name", name)
+        span:  Span,
+    },
+}
+
+// pattern if guard => body
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard:   Option<Expression>,
+    pub body:    Vec<Statement>,
+}
+
+// x, y = 10, [a, b] or ...rest in a parameter list
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    pub pattern: Pattern, // always an ident for a rest parameter
+    pub default: Option<Expression>,
+    pub rest:    bool,
 }
 // pub type BlockStatement = Vec<Statement>;
 
@@ -16,42 +54,106 @@ pub trait Node {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
-    Undefined,
     Ident(Identifier),
     IntegerLiteral {
         token: TokenType,
         value: i64,
+        span:  Span,
+    },
+    FloatLiteral {
+        token: TokenType,
+        value: f64,
+        span:  Span,
+    },
+    StringLiteral {
+        token: TokenType,
+        value: String,
+        span:  Span,
     },
     Prefix {
         token:    TokenType,
         operator: String,
         right:    Box<Expression>,
+        span:     Span,
     },
     Infix {
         token:    TokenType,
         left:     Box<Expression>,
         operator: String,
         right:    Box<Expression>,
+        span:     Span,
     },
     Bool {
         token: TokenType,
         value: bool,
+        span:  Span,
     },
     If {
         token:       TokenType,
         condition:   Box<Expression>,
         consequence: Vec<Statement>,
         alternative: Vec<Statement>,
+        span:        Span,
+    },
+    Try {
+        token:     TokenType, // token::TRY
+        body:      Vec<Statement>,
+        param:     Option<Box<Pattern>>, // catch (e) { }, None for catch { }
+        handler:   Option<Vec<Statement>>,
+        finalizer: Option<Vec<Statement>>,
+        span:      Span,
+    },
+    Match {
+        token:   TokenType, // token::MATCH
+        subject: Box<Expression>,
+        arms:    Vec<MatchArm>,
+        span:    Span,
     },
     FunctionLiteral {
         token:      TokenType,
-        parameters: Vec<Identifier>,
+        parameters: Vec<Parameter>,
         body:       Vec<Statement>,
+        span:       Span,
     },
     FunctionCall {
         token: TokenType,       // token::LPAREN
         func:  Box<Expression>, // functionliteral or ident
         args:  Vec<Expression>,
+        named: Vec<(Identifier, Expression)>, // f(x: 1)
+        span:  Span,
+    },
+    ArrayLiteral {
+        token:    TokenType, // token::LBRACKET
+        elements: Vec<Expression>,
+        span:     Span,
+    },
+    HashLiteral {
+        token: TokenType, // token::LBRACE
+        pairs: Vec<(Expression, Expression)>,
+        span:  Span,
+    },
+    Index {
+        token: TokenType, // token::LBRACKET
+        left:  Box<Expression>,
+        index: Box<Expression>,
+        span:  Span,
+    },
+    Propagate {
+        token: TokenType,       // token::QUESTION
+        value: Box<Expression>, // x? gives what is in Ok or Some, and returns Err or None
+        span:  Span,
+    },
+    Block {
+        token:      TokenType, // token::LBRACE
+        statements: Vec<Statement>,
+        span:       Span,
+    },
+    Assign {
+        token:    TokenType, // token::ASSIGN or a compound one like token::PLUS_ASSIGN
+        target:   Box<Expression>, // a place, see Expression::is_place
+        operator: String,
+        value:    Box<Expression>,
+        span:     Span,
     },
 }
 impl Node for Expression {
@@ -63,17 +165,69 @@ impl Node for Expression {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
     Let {
-        token: TokenType, // token::LET
-        name:  Identifier,
-        value: Expression,
+        token:   TokenType, // token::LET
+        pattern: Pattern,
+        value:   Expression,
+        span:    Span,
+    },
+    Const {
+        token:   TokenType, // token::CONST
+        pattern: Pattern,
+        value:   Expression,
+        span:    Span,
+    },
+    Function {
+        token:      TokenType, // token::FUNC
+        name:       Identifier,
+        parameters: Vec<Parameter>,
+        body:       Vec<Statement>,
+        span:       Span,
     },
     Return {
         token: TokenType, // token::RETURN
         value: Expression,
+        span:  Span,
+    },
+    Throw {
+        token: TokenType, // token::THROW
+        value: Expression,
+        span:  Span,
+    },
+    Defer {
+        token: TokenType,  // token::DEFER
+        value: Expression, // evaluated when the function it is in returns
+        span:  Span,
     },
     Expr {
         token:      TokenType, //expression 의 첫 토큰
         expression: Expression,
+        span:       Span,
+    },
+    While {
+        token:     TokenType, // token::WHILE
+        label:     Option<String>,
+        condition: Expression,
+        body:      Vec<Statement>,
+        span:      Span,
+    },
+    For {
+        token:     TokenType, // token::FOR
+        label:     Option<String>,
+        init:      Option<Box<Statement>>,
+        condition: Option<Expression>,
+        step:      Option<Expression>,
+        body:      Vec<Statement>,
+        span:      Span,
+    },
+    Break {
+        token: TokenType, // token::BREAK
+        label: Option<String>,
+        span:  Span,
+    },
+    Continue {
+        token: TokenType, // token::CONTINUE
+        label: Option<String>,
+        span:  Span,
     },
 }
 impl Node for Statement {
@@ -1,102 +1,317 @@
-use super::{Expression, Program, Statement};
+use super::{Expression, Identifier, MatchArm, Parameter, Pattern, Program, Span, Statement};
+use std::fmt::{Display, Formatter};
 
-impl Expression {
-    pub fn to_string(&self) -> String {
+fn join<T: Display>(items: &[T], sep: &str) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join(sep)
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Ident(i) => format!("{}", i.value),
-            Self::IntegerLiteral { token: _, value } => format!("{}", value),
+            Self::Ident(i) => write!(f, "{}", i.value),
+            Self::IntegerLiteral { value, .. } => write!(f, "{}", value),
+            Self::FloatLiteral { value, .. } => write!(f, "{:?}", value),
+            Self::StringLiteral { value, .. } => write!(f, "{:?}", value),
             Self::Prefix {
-                token: _,
-                operator,
-                right,
-            } => format!("({}{})", operator, right.to_string()),
+                operator, right, ..
+            } => write!(f, "({}{})", operator, right),
             Self::Infix {
-                token: _,
                 left,
                 operator,
                 right,
-            } => format!("({} {} {})", left.to_string(), operator, right.to_string()),
-            Self::Bool { token: _, value } => format!("{}", value),
+                ..
+            } => write!(f, "({} {} {})", left, operator, right),
+            Self::Bool { value, .. } => write!(f, "{}", value),
             Self::If {
-                token: _,
                 condition,
                 consequence,
                 alternative,
+                ..
             } => {
-                if !alternative.is_empty() {
-                    format!(
-                        "if({}) {{ {} }} else {{ {} }}",
-                        condition.to_string(),
-                        consequence.iter().fold("".to_owned(), |acc, a| acc
-                            + &" ".to_owned()
-                            + &a.to_string())[1..]
-                            .to_owned(),
-                        alternative.iter().fold("".to_owned(), |acc, a| acc
-                            + &" ".to_owned()
-                            + &a.to_string())[1..]
-                            .to_owned()
-                    )
-                }
-                else {
-                    format!(
-                        "if({}) {{ {} }}",
-                        condition.to_string(),
-                        consequence.iter().fold("".to_owned(), |acc, a| acc
-                            + &" ".to_owned()
-                            + &a.to_string())[1..]
-                            .to_owned()
-                    )
+                write!(f, "if({}) {{ {} }}", condition, join(consequence, " "))?;
+                match alternative.as_slice() {
+                    [] => Ok(()),
+                    // else if (..) { } is kept as an if alone in the else block
+                    [Statement::Expr {
+                        expression: elif @ Self::If { .. },
+                        ..
+                    }] => write!(f, " else {}", elif),
+                    _ => write!(f, " else {{ {} }}", join(alternative, " ")),
                 }
             }
-            Self::FunctionLiteral {
-                token: _,
-                parameters,
+            Self::Try {
                 body,
+                param,
+                handler,
+                finalizer,
+                ..
             } => {
-                format!(
-                    "fn({}) {{ {} }}",
-                    parameters
-                        .iter()
-                        .fold("".to_owned(), |acc, a| acc + &", ".to_owned() + &a.value)[2..]
-                        .to_owned(),
-                    body.iter().fold("".to_owned(), |acc, a| acc
-                        + &", ".to_owned()
-                        + &a.to_string())[2..]
-                        .to_owned()
-                )
+                write!(f, "try {{ {} }}", join(body, " "))?;
+                if let Some(handler) = handler {
+                    match param {
+                        Some(param) => write!(f, " catch ({}) ", param)?,
+                        None => write!(f, " catch ")?,
+                    }
+                    write!(f, "{{ {} }}", join(handler, " "))?;
+                }
+                if let Some(finalizer) = finalizer {
+                    write!(f, " finally {{ {} }}", join(finalizer, " "))?;
+                }
+                Ok(())
             }
+            Self::Match { subject, arms, .. } => {
+                write!(f, "match({}) {{ {} }}", subject, join(arms, ", "))
+            }
+            Self::FunctionLiteral {
+                parameters, body, ..
+            } => write!(
+                f,
+                "fn({}) {{ {} }}",
+                join(parameters, ", "),
+                join(body, " ")
+            ),
             Self::FunctionCall {
-                token: _,
-                func,
-                args,
+                func, args, named, ..
             } => {
-                format!(
-                    "{}({})",
-                    func.to_string(),
-                    args.iter().fold("".to_owned(), |acc, a| acc
-                        + &", ".to_owned()
-                        + &a.to_string())[2..]
-                        .to_owned()
-                )
+                let args = args
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .chain(
+                        named
+                            .iter()
+                            .map(|(name, arg)| format!("{}: {}", name.value, arg)),
+                    )
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{}({})", func, args)
+            }
+            Self::ArrayLiteral { elements, .. } => write!(f, "[{}]", join(elements, ", ")),
+            Self::HashLiteral { pairs, .. } => {
+                let pairs = pairs
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{{{}}}", pairs)
             }
-            _ => format!("Undefined"),
+            Self::Index { left, index, .. } => write!(f, "({}[{}])", left, index),
+            Self::Propagate { value, .. } => write!(f, "({}?)", value),
+            Self::Block { statements, .. } => write!(f, "{{ {} }}", join(statements, " ")),
+            Self::Assign {
+                target,
+                operator,
+                value,
+                ..
+            } => write!(f, "({} {} {})", target, operator, value),
         }
     }
 }
 
-impl Statement {
-    pub fn to_string(&self) -> String {
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Ident(i) => i.span,
+            Self::IntegerLiteral { span, .. }
+            | Self::FloatLiteral { span, .. }
+            | Self::StringLiteral { span, .. }
+            | Self::Prefix { span, .. }
+            | Self::Infix { span, .. }
+            | Self::Bool { span, .. }
+            | Self::If { span, .. }
+            | Self::Try { span, .. }
+            | Self::Match { span, .. }
+            | Self::FunctionLiteral { span, .. }
+            | Self::FunctionCall { span, .. }
+            | Self::ArrayLiteral { span, .. }
+            | Self::HashLiteral { span, .. }
+            | Self::Index { span, .. }
+            | Self::Propagate { span, .. }
+            | Self::Block { span, .. }
+            | Self::Assign { span, .. } => *span,
+        }
+    }
+
+    // what can be assigned to, a variable or an element of one like a[0]["k"]
+    pub fn is_place(&self) -> bool {
+        match self {
+            Self::Ident(_) => true,
+            Self::Index { left, .. } => left.is_place(),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Let {
-                token: _,
+            Self::Ident(i) => write!(f, "{}", i.value),
+            // -1 rather than (-1)
+            Self::Literal(Expression::Prefix {
+                operator, right, ..
+            }) => write!(f, "{}{}", operator, right),
+            Self::Literal(literal) => write!(f, "{}", literal),
+            Self::Wildcard(_) => write!(f, "_"),
+            Self::Array { elements, rest, .. } => {
+                let mut elements = elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>();
+                if let Some(rest) = rest {
+                    elements.push(format!("...{}", rest.value));
+                }
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Self::Hash { pairs, .. } => {
+                let pairs = pairs
+                    .iter()
+                    .map(|(key, pattern)| match pattern {
+                        Self::Ident(i) if i.value == *key => key.clone(),
+                        _ => format!("{}: {}", key, pattern),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{{{}}}", pairs)
+            }
+        }
+    }
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Ident(i) => i.span,
+            Self::Literal(literal) => literal.span(),
+            Self::Wildcard(span) | Self::Array { span, .. } | Self::Hash { span, .. } => *span,
+        }
+    }
+
+    // every identifier the pattern binds, from left to right
+    pub fn names(&self) -> Vec<&Identifier> {
+        match self {
+            Self::Ident(i) => vec![i],
+            Self::Literal(_) | Self::Wildcard(_) => vec![],
+            Self::Array { elements, rest, .. } => elements
+                .iter()
+                .flat_map(|element| element.names())
+                .chain(rest)
+                .collect(),
+            Self::Hash { pairs, .. } => pairs
+                .iter()
+                .flat_map(|(_, pattern)| pattern.names())
+                .collect(),
+        }
+    }
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)?;
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+        write!(f, " => {{ {} }}", join(&self.body, " "))
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.default {
+            _ if self.rest => write!(f, "...{}", self.pattern),
+            Some(default) => write!(f, "{} = {}", self.pattern, default),
+            None => write!(f, "{}", self.pattern),
+        }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Let { pattern, value, .. } => write!(f, "let {} = {};", pattern, value),
+            Self::Const { pattern, value, .. } => write!(f, "const {} = {};", pattern, value),
+            Self::Function {
                 name,
-                value,
-            } => format!("let {} = {};", name.value, value.to_string()),
-            Self::Return { token: _, value } => format!("return {};", value.to_string()),
-            Self::Expr {
-                token: _,
-                expression,
-            } => format!("{}", expression.to_string()),
+                parameters,
+                body,
+                ..
+            } => write!(
+                f,
+                "fn {}({}) {{ {} }}",
+                name.value,
+                join(parameters, ", "),
+                join(body, " ")
+            ),
+            Self::Return { value, .. } => write!(f, "return {};", value),
+            Self::Throw { value, .. } => write!(f, "throw {};", value),
+            Self::Defer { value, .. } => write!(f, "defer {};", value),
+            Self::Expr { expression, .. } => write!(f, "{}", expression),
+            Self::While {
+                label,
+                condition,
+                body,
+                ..
+            } => write!(
+                f,
+                "{}while({}) {{ {} }}",
+                label_prefix(label),
+                condition,
+                join(body, " ")
+            ),
+            Self::For {
+                label,
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => write!(
+                f,
+                "{}for({}; {}; {}) {{ {} }}",
+                label_prefix(label),
+                init.as_ref().map_or(String::new(), |s| s
+                    .to_string()
+                    .trim_end_matches(';')
+                    .to_string()),
+                condition.as_ref().map_or(String::new(), |c| c.to_string()),
+                step.as_ref().map_or(String::new(), |s| s.to_string()),
+                join(body, " ")
+            ),
+            Self::Break { label, .. } => match label {
+                Some(label) => write!(f, "break {};", label),
+                None => write!(f, "break;"),
+            },
+            Self::Continue { label, .. } => match label {
+                Some(label) => write!(f, "continue {};", label),
+                None => write!(f, "continue;"),
+            },
+        }
+    }
+}
+
+fn label_prefix(label: &Option<String>) -> String {
+    match label {
+        Some(label) => format!("{}: ", label),
+        None => String::new(),
+    }
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Let { span, .. }
+            | Self::Const { span, .. }
+            | Self::Function { span, .. }
+            | Self::Return { span, .. }
+            | Self::Throw { span, .. }
+            | Self::Defer { span, .. }
+            | Self::Expr { span, .. }
+            | Self::While { span, .. }
+            | Self::For { span, .. }
+            | Self::Break { span, .. }
+            | Self::Continue { span, .. } => *span,
         }
     }
 }
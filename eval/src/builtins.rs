@@ -0,0 +1,148 @@
+use super::{error, Eval};
+
+// Ok, Err and Some make Results and Options, None is one too, see look_up_builtin
+const BUILTINS: [object::Builtin; 9] = [
+    object::Builtin {
+        name:  "Ok",
+        arity: 1,
+        func:  |mut args| {
+            Box::new(object::ResultValue {
+                ok:    true,
+                value: args.remove(0),
+            })
+        },
+    },
+    object::Builtin {
+        name:  "Err",
+        arity: 1,
+        func:  |mut args| {
+            Box::new(object::ResultValue {
+                ok:    false,
+                value: args.remove(0),
+            })
+        },
+    },
+    object::Builtin {
+        name:  "Some",
+        arity: 1,
+        func:  |mut args| {
+            Box::new(object::OptionValue {
+                value: Some(args.remove(0)),
+            })
+        },
+    },
+    object::Builtin {
+        name:  "is_ok",
+        arity: 1,
+        func:  |args| is_variant("is_ok", &*args[0], "Result", true),
+    },
+    object::Builtin {
+        name:  "is_err",
+        arity: 1,
+        func:  |args| is_variant("is_err", &*args[0], "Result", false),
+    },
+    object::Builtin {
+        name:  "is_some",
+        arity: 1,
+        func:  |args| is_variant("is_some", &*args[0], "Option", true),
+    },
+    object::Builtin {
+        name:  "is_none",
+        arity: 1,
+        func:  |args| is_variant("is_none", &*args[0], "Option", false),
+    },
+    object::Builtin {
+        name:  "unwrap",
+        arity: 1,
+        func:  |args| match Eval::unwrapped(&*args[0]) {
+            Some(Some(value)) => value,
+            Some(None) => Eval::new_error(
+                error::UNWRAP_FAILED,
+                format!("Unwrap Failed: {}", args[0].inspect()),
+            ),
+            None => type_mismatch("unwrap", &*args[0]),
+        },
+    },
+    object::Builtin {
+        name:  "unwrap_or",
+        arity: 2,
+        func:  |mut args| match Eval::unwrapped(&*args[0]) {
+            Some(Some(value)) => value,
+            Some(None) => args.remove(1),
+            None => type_mismatch("unwrap_or", &*args[0]),
+        },
+    },
+];
+
+impl Eval {
+    // what a name means when no env has it
+    pub(super) fn look_up_builtin(name: &str) -> Option<Box<dyn object::Object>> {
+        if name == "None" {
+            return Some(Box::new(object::OptionValue { value: None }));
+        }
+        BUILTINS
+            .iter()
+            .find(|b| b.name == name)
+            .map(|b| Box::new(b.clone()) as Box<dyn object::Object>)
+    }
+
+    pub(super) fn call_builtin(
+        builtin: &object::Builtin,
+        args: Vec<Box<dyn object::Object>>,
+        named: Vec<(String, Box<dyn object::Object>)>,
+    ) -> Box<dyn object::Object> {
+        if let Some((key, _)) = named.first() {
+            return Eval::new_error(
+                error::WRONG_ARGUMENTS,
+                format!("Unknown Argument: {} of {}", key, builtin.name),
+            );
+        }
+        if args.len() != builtin.arity {
+            return Eval::new_error(
+                error::WRONG_ARGUMENTS,
+                format!(
+                    "Wrong number of Arguments: {} expected {}, got {}",
+                    builtin.name,
+                    builtin.arity,
+                    args.len()
+                ),
+            );
+        }
+        (builtin.func)(args)
+    }
+
+    // Some(what an Ok or Some holds), Some(None) for an Err or None,
+    // and None for a value which is neither a Result nor an Option
+    pub(super) fn unwrapped(value: &dyn object::Object) -> Option<Option<Box<dyn object::Object>>> {
+        if let Some(result) = value.as_any().downcast_ref::<object::ResultValue>() {
+            Some(result.ok.then(|| result.value.clone()))
+        }
+        else {
+            value
+                .as_any()
+                .downcast_ref::<object::OptionValue>()
+                .map(|option| option.value.clone())
+        }
+    }
+}
+
+// is_ok and is_some are true for the first variant, is_err and is_none for the other
+fn is_variant(
+    name: &str,
+    value: &dyn object::Object,
+    object_type: &str,
+    first: bool,
+) -> Box<dyn object::Object> {
+    if value.object_type() != object_type {
+        return type_mismatch(name, value);
+    }
+    let is_first = Eval::unwrapped(value).unwrap().is_some();
+    Box::new(object::static_bool_obj(is_first == first))
+}
+
+fn type_mismatch(name: &str, value: &dyn object::Object) -> Box<dyn object::Object> {
+    Eval::new_error(
+        error::TYPE_MISMATCH,
+        format!("Type Mismatched: {}({})", name, value.object_type()),
+    )
+}
@@ -0,0 +1,19 @@
+// codes of runtime errors, a caught error carries one as its "code"
+pub const TYPE_MISMATCH: &str = "EVL0001";
+pub const UNKNOWN_OPERATOR: &str = "EVL0002";
+pub const IDENT_NOT_FOUND: &str = "EVL0003";
+pub const DIVISION_BY_ZERO: &str = "EVL0004";
+pub const INTEGER_OVERFLOW: &str = "EVL0005";
+pub const NEGATIVE_EXPONENT: &str = "EVL0006";
+pub const INVALID_SHIFT: &str = "EVL0007";
+pub const INDEX_OUT_OF_BOUNDS: &str = "EVL0008";
+pub const INDEX_NOT_SUPPORTED: &str = "EVL0009";
+pub const UNUSABLE_AS_HASH_KEY: &str = "EVL0010";
+pub const NOT_A_FUNCTION: &str = "EVL0011";
+pub const WRONG_ARGUMENTS: &str = "EVL0012";
+pub const ASSIGN_TO_CONST: &str = "EVL0013";
+pub const INVALID_ASSIGN: &str = "EVL0014";
+pub const CANNOT_DESTRUCTURE: &str = "EVL0015";
+pub const NON_EXHAUSTIVE_MATCH: &str = "EVL0016";
+pub const THROWN: &str = "EVL0017";
+pub const UNWRAP_FAILED: &str = "EVL0018";
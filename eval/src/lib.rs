@@ -3,168 +3,318 @@ use std::{cell::RefCell, rc::Rc};
 use object::Env;
 use parser::Parser;
 
+mod builtins;
+mod error;
 mod unit_eval;
 mod util;
+pub use error::*;
+
+// how integer arithmetic behaves when the result does not fit in i64
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+// an expression of a defer statement and the env it is evaluated in
+type Deferred = (ast::Expression, Rc<RefCell<Env>>);
 
 pub struct Eval {
-    env: Rc<RefCell<Env>>,
+    env:      Rc<RefCell<Env>>,
+    overflow: Overflow,
+    // what each function call being evaluated has deferred, the innermost last
+    defers:   RefCell<Vec<Vec<Deferred>>>,
+}
+
+impl Default for Eval {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Eval {
     pub fn new() -> Self {
-        Self { env: Env::new() }
+        Self::with_overflow(Overflow::default())
+    }
+
+    pub fn with_overflow(overflow: Overflow) -> Self {
+        Self {
+            env: Env::new(),
+            overflow,
+            defers: RefCell::new(vec![]),
+        }
     }
 
     pub fn run(&self, input: String) -> String {
-        let program = Parser::parse(input);
+        let program = match Parser::parse(input) {
+            Ok(program) => program,
+            Err(errors) => {
+                return errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+        };
         if program.is_empty() {
             return String::new();
         }
-        let e = Eval::_eval(&program, &self.env);
+        let e = self._eval(&program, &self.env);
         e.inspect()
     }
 
-    fn _eval(node: &dyn ast::Node, env: &Rc<RefCell<Env>>) -> Box<dyn object::Object> {
+    fn _eval(&self, node: &dyn ast::Node, env: &Rc<RefCell<Env>>) -> Box<dyn object::Object> {
         let nd = node.as_any();
         if let Some(program) = nd.downcast_ref::<ast::Program>() {
-            Eval::eval_program(&program.statements, env)
+            self.eval_program(&program.statements, env)
         }
         else if let Some(statement) = nd.downcast_ref::<ast::Statement>() {
             match statement {
-                ast::Statement::Expr {
-                    token: _,
-                    expression,
-                } => Eval::_eval(expression, env),
-                ast::Statement::Return { token: _, value } => {
-                    let value = Eval::_eval(value, env);
-                    if Eval::is_error(&value) {
+                ast::Statement::Expr { expression, .. } => self._eval(expression, env),
+                // bound before the statements of its scope run, see hoist_functions
+                ast::Statement::Function { .. } => Box::new(object::NULL),
+                ast::Statement::Return { value, .. } => {
+                    let value = self._eval(value, env);
+                    if Eval::is_unwinding(&*value) {
                         return value;
                     }
                     Box::new(object::ReturnValue { value })
                 }
-                ast::Statement::Let {
-                    token: _,
-                    name,
-                    value,
-                } => {
-                    let value = Eval::_eval(value, env);
-                    if Eval::is_error(&value) {
+                ast::Statement::Throw { value, .. } => {
+                    let value = self._eval(value, env);
+                    if Eval::is_unwinding(&*value) {
                         return value;
                     }
-                    env.borrow_mut().set(name.value.clone(), value.clone());
-                    // println!("==> {:?}", env.get(&name.value.clone()));
-                    Box::new(object::NULL) // remove output in let stmt
+                    let thrown = Box::new(object::Error {
+                        value:  format!("Uncaught: {}", value.inspect()),
+                        code:   error::THROWN,
+                        span:   None,
+                        thrown: Some(value),
+                    });
+                    Eval::with_span(thrown, statement.span())
+                }
+                ast::Statement::Defer { value, .. } => {
+                    if let Some(deferred) = self.defers.borrow_mut().last_mut() {
+                        deferred.push((value.clone(), Rc::clone(env)));
+                    }
+                    Box::new(object::NULL)
+                }
+                ast::Statement::Let { pattern, value, .. } => {
+                    let rlt = self.eval_declaration(pattern, value, false, env);
+                    Eval::with_span(rlt, statement.span())
                 }
+                ast::Statement::Const { pattern, value, .. } => {
+                    let rlt = self.eval_declaration(pattern, value, true, env);
+                    Eval::with_span(rlt, statement.span())
+                }
+                ast::Statement::While {
+                    label,
+                    condition,
+                    body,
+                    ..
+                } => self.eval_while_statement(label, condition, body, env),
+                ast::Statement::For {
+                    label,
+                    init,
+                    condition,
+                    step,
+                    body,
+                    ..
+                } => self.eval_for_statement(label, init, condition, step, body, env),
+                ast::Statement::Break { label, .. } => Box::new(object::Break {
+                    label: label.clone(),
+                }),
+                ast::Statement::Continue { label, .. } => Box::new(object::Continue {
+                    label: label.clone(),
+                }),
             }
         }
         else if let Some(expression) = nd.downcast_ref::<ast::Expression>() {
-            match expression {
-                ast::Expression::IntegerLiteral { token: _, value } => {
-                    Box::new(object::Integer { value: *value })
-                }
-                ast::Expression::Bool { token: _, value } => {
-                    Box::new(object::static_bool_obj(*value))
-                }
-                ast::Expression::Prefix {
-                    token: _,
-                    operator,
-                    right,
-                } => {
-                    let right = Eval::_eval(&**right, env);
-                    if Eval::is_error(&right) {
-                        return right;
-                    }
-                    return Eval::eval_prefix_expression(operator, right);
-                }
-                ast::Expression::Infix {
-                    token: _,
-                    left,
-                    operator,
-                    right,
-                } => {
-                    let _left = Eval::_eval(&**left, env);
-                    if Eval::is_error(&_left) {
-                        return _left;
+            let rlt = self.eval_expression(expression, env);
+            Eval::with_span(rlt, expression.span())
+        }
+        else {
+            Box::new(object::Null {}) // TODO : how to handle?
+        }
+    }
+
+    fn eval_expression(
+        &self,
+        expression: &ast::Expression,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        match expression {
+            ast::Expression::IntegerLiteral { value, .. } => {
+                Box::new(object::Integer { value: *value })
+            }
+            ast::Expression::FloatLiteral { value, .. } => {
+                Box::new(object::Float { value: *value })
+            }
+            ast::Expression::StringLiteral { value, .. } => Box::new(object::Str {
+                value: value.clone(),
+            }),
+            ast::Expression::Bool { value, .. } => Box::new(object::static_bool_obj(*value)),
+            ast::Expression::Prefix {
+                operator, right, ..
+            } => {
+                let right = self._eval(&**right, env);
+                if Eval::is_unwinding(&*right) {
+                    return right;
+                }
+                self.eval_prefix_expression(operator, right)
+            }
+            ast::Expression::Infix {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                let _left = self._eval(&**left, env);
+                if Eval::is_unwinding(&*_left) {
+                    return _left;
+                }
+
+                // the right side is only evaluated when it can change the result
+                if operator == "&&" || operator == "||" {
+                    if Eval::is_true(&*_left) == (operator == "||") {
+                        return Box::new(object::static_bool_obj(operator == "||"));
                     }
-                    let right = Eval::_eval(&**right, env);
-                    if Eval::is_error(&right) {
+                    let right = self._eval(&**right, env);
+                    if Eval::is_unwinding(&*right) {
                         return right;
                     }
+                    return Box::new(object::static_bool_obj(Eval::is_true(&*right)));
+                }
 
-                    if operator == "=" {
-                        let name = match &**left {
-                            ast::Expression::Ident(ast::Identifier { token: _, value }) => value,
-                            _ => {
-                                return Eval::new_error(
-                                    "Cannot Assign to non-identifier".to_string(),
-                                )
-                            }
-                        };
-                        env.borrow_mut().set(name.to_owned(), right.clone());
-                        // println!("==> {:?}", env.get(&name.value.clone()));
-                        return Box::new(object::NULL); // remove output in assign stmt;
-                    }
-                    return Eval::eval_infix_expression(operator, _left, right);
+                let right = self._eval(&**right, env);
+                if Eval::is_unwinding(&*right) {
+                    return right;
                 }
-                ast::Expression::If {
-                    token: _,
-                    condition,
-                    consequence,
-                    alternative,
-                } => {
-                    let condition = &**condition;
-                    let condition = Eval::_eval(condition, env);
-                    if Eval::is_error(&condition) {
-                        return condition;
-                    }
 
-                    if Eval::is_true(&condition) {
-                        Eval::eval_statements(consequence, env)
-                    }
-                    else {
-                        Eval::eval_statements(alternative, env)
-                    }
+                self.eval_infix_expression(operator, _left, right)
+            }
+            ast::Expression::Assign {
+                target,
+                operator,
+                value,
+                ..
+            } => {
+                let value = self._eval(&**value, env);
+                if Eval::is_unwinding(&*value) {
+                    return value;
                 }
-                ast::Expression::Ident(ast::Identifier { token: _, value }) => {
-                    Eval::eval_ident(value, env)
+                self.assign_place(target, operator, value, env)
+            }
+            ast::Expression::If {
+                condition,
+                consequence,
+                alternative,
+                ..
+            } => {
+                let condition = &**condition;
+                let condition = self._eval(condition, env);
+                if Eval::is_unwinding(&*condition) {
+                    return condition;
                 }
-                ast::Expression::FunctionLiteral {
-                    token: _,
-                    parameters,
-                    body,
-                } => Box::new(object::Function {
-                    parameters: parameters.to_vec(),
-                    body:       body.to_vec(),
-                    env:        Rc::clone(env), // todo: need to be ref / it is very high cost #issue 24
-                }),
-                ast::Expression::FunctionCall {
-                    token: _,
-                    func,
-                    args,
-                } => {
-                    let func = Eval::_eval(&**func, env);
-
-                    if Eval::is_error(&func) {
-                        return func;
-                    }
-                    let func = Box::new(match func.as_any().downcast_ref::<object::Function>() {
-                        Some(v) => v.clone(),
-                        None => return Eval::new_error("Not a func".to_string()),
-                    });
-                    // above, more fancy? is there way?
 
-                    let args = Eval::eval_expressions(args, env);
-                    if args.len() == 1 && Eval::is_error(args.first().unwrap()) {
-                        return args[0].clone(); // wanna change it to more safe
+                if Eval::is_true(&*condition) {
+                    self.eval_block(consequence, env)
+                }
+                else {
+                    self.eval_block(alternative, env)
+                }
+            }
+            ast::Expression::Block { statements, .. } => self.eval_block(statements, env),
+            ast::Expression::Ident(ast::Identifier { value, .. }) => Eval::eval_ident(value, env),
+            ast::Expression::FunctionLiteral {
+                parameters, body, ..
+            } => Box::new(object::Function {
+                name:       None,
+                parameters: parameters.to_vec(),
+                body:       body.to_vec(),
+                env:        Rc::clone(env), // todo: need to be ref / it is very high cost #issue 24
+            }),
+            ast::Expression::Try {
+                body,
+                param,
+                handler,
+                finalizer,
+                ..
+            } => self.eval_try_expression(body, param, handler, finalizer, env),
+            ast::Expression::Match { subject, arms, .. } => {
+                self.eval_match_expression(subject, arms, env)
+            }
+            ast::Expression::FunctionCall {
+                func, args, named, ..
+            } => {
+                let func = self._eval(&**func, env);
+
+                if Eval::is_unwinding(&*func) {
+                    return func;
+                }
+                if !matches!(func.object_type(), "Function" | "Builtin") {
+                    return Eval::new_error(error::NOT_A_FUNCTION, "Not a func".to_string());
+                }
+
+                let args = self.eval_expressions(args, env);
+                if args.len() == 1 && Eval::is_unwinding(&*args[0]) {
+                    return args[0].clone(); // wanna change it to more safe
+                }
+                let mut named_args = vec![];
+                for (name, arg) in named {
+                    let arg = self._eval(arg, env);
+                    if Eval::is_unwinding(&*arg) {
+                        return arg;
                     }
+                    named_args.push((name.value.clone(), arg));
+                }
 
-                    Eval::put_args_in_function(func, args)
+                match func.as_any().downcast_ref::<object::Function>() {
+                    Some(func) => {
+                        self.put_args_in_function(Box::new(func.clone()), args, named_args)
+                    }
+                    None => {
+                        let builtin = func.as_any().downcast_ref::<object::Builtin>().unwrap();
+                        Eval::call_builtin(builtin, args, named_args)
+                    }
                 }
-                _ => Box::new(object::Null {}), // TODO : how to handle?
             }
-        }
-        else {
-            Box::new(object::Null {}) // TODO : how to handle?
+            ast::Expression::ArrayLiteral { elements, .. } => {
+                let elements = self.eval_expressions(elements, env);
+                if elements.len() == 1 && Eval::is_unwinding(&*elements[0]) {
+                    return elements[0].clone();
+                }
+                Box::new(object::Array { elements })
+            }
+            ast::Expression::HashLiteral { pairs, .. } => self.eval_hash_literal(pairs, env),
+            ast::Expression::Propagate { value, .. } => {
+                let value = self._eval(&**value, env);
+                if Eval::is_unwinding(&*value) {
+                    return value;
+                }
+                // an Err or None leaves the function as if it were returned
+                match Eval::unwrapped(&*value) {
+                    Some(Some(inner)) => inner,
+                    Some(None) => Box::new(object::ReturnValue { value }),
+                    None => Eval::new_error(
+                        error::TYPE_MISMATCH,
+                        format!("Type Mismatched: {}?", value.object_type()),
+                    ),
+                }
+            }
+            ast::Expression::Index { left, index, .. } => {
+                let left = self._eval(&**left, env);
+                if Eval::is_unwinding(&*left) {
+                    return left;
+                }
+                let index = self._eval(&**index, env);
+                if Eval::is_unwinding(&*index) {
+                    return index;
+                }
+                Eval::eval_index_expression(left, index)
+            }
         }
     }
 }
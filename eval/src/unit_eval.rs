@@ -1,16 +1,20 @@
 use std::{cell::RefCell, rc::Rc};
 
-use super::Eval;
-use object::Env;
+use super::{error, Eval, Overflow};
+use object::{Env, EnvError};
 
 impl Eval {
     pub(super) fn eval_program(
+        &self,
         stmts: &Vec<ast::Statement>,
         env: &Rc<RefCell<Env>>,
     ) -> Box<dyn object::Object> {
+        if let Some(err) = self.hoist_functions(stmts, env) {
+            return err;
+        }
         let mut rlt: Box<dyn object::Object> = Box::new(object::Null {});
         for stmt in stmts {
-            rlt = Eval::_eval(stmt, env);
+            rlt = self._eval(stmt, env);
             if rlt.object_type() == "ReturnValue" {
                 let rlt = rlt
                     .as_any()
@@ -28,31 +32,285 @@ impl Eval {
     }
 
     pub(super) fn eval_statements(
+        &self,
         stmts: &Vec<ast::Statement>,
         env: &Rc<RefCell<Env>>,
     ) -> Box<dyn object::Object> {
+        if let Some(err) = self.hoist_functions(stmts, env) {
+            return err;
+        }
         let mut rlt: Box<dyn object::Object> = Box::new(object::Null {});
         for stmt in stmts {
-            rlt = Eval::_eval(stmt, env);
+            rlt = self._eval(stmt, env);
             // println!("{} {}", stmt.to_string(), rlt.inspect());
-            if rlt.object_type() == "ReturnValue" {
-                return rlt;
+            match rlt.object_type() {
+                "ReturnValue" | "Error" | "Break" | "Continue" => return rlt,
+                _ => {}
             }
-            else if rlt.object_type() == "Error" {
-                return rlt;
+        }
+        rlt
+    }
+
+    // every fn declaration of a scope is bound before its first statement runs,
+    // so declared functions can call each other in any order
+    pub(super) fn hoist_functions(
+        &self,
+        stmts: &[ast::Statement],
+        env: &Rc<RefCell<Env>>,
+    ) -> Option<Box<dyn object::Object>> {
+        for stmt in stmts {
+            if let ast::Statement::Function {
+                name,
+                parameters,
+                body,
+                span,
+                ..
+            } = stmt
+            {
+                let func = Box::new(object::Function {
+                    name:       Some(name.value.clone()),
+                    parameters: parameters.to_vec(),
+                    body:       body.to_vec(),
+                    env:        Rc::clone(env),
+                });
+                if env
+                    .borrow_mut()
+                    .declare(name.value.clone(), func, false)
+                    .is_err()
+                {
+                    let err = Eval::new_error(
+                        error::ASSIGN_TO_CONST,
+                        format!("Cannot Redeclare const: {}", name.value),
+                    );
+                    return Some(Eval::with_span(err, *span));
+                }
+            }
+        }
+        None
+    }
+
+    pub(super) fn eval_declaration(
+        &self,
+        pattern: &ast::Pattern,
+        value: &ast::Expression,
+        constant: bool,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        let value = self._eval(value, env);
+        if Eval::is_unwinding(&*value) {
+            return value;
+        }
+        let mut bindings = vec![];
+        if let Err(err) = self.destructure(pattern, value, env, &mut bindings) {
+            return err;
+        }
+        for (name, value) in bindings {
+            if env
+                .borrow_mut()
+                .declare(name.clone(), value, constant)
+                .is_err()
+            {
+                return Eval::new_error(
+                    error::ASSIGN_TO_CONST,
+                    format!("Cannot Redeclare const: {}", name),
+                );
+            }
+        }
+        Box::new(object::NULL) // remove output in let stmt
+    }
+
+    // pairs every name in the pattern with the part of value it stands for,
+    // fails when the value does not have the shape of the pattern
+    pub(super) fn destructure(
+        &self,
+        pattern: &ast::Pattern,
+        value: Box<dyn object::Object>,
+        env: &Rc<RefCell<Env>>,
+        bindings: &mut Vec<(String, Box<dyn object::Object>)>,
+    ) -> Result<(), Box<dyn object::Object>> {
+        match pattern {
+            ast::Pattern::Ident(ident) => bindings.push((ident.value.clone(), value)),
+            ast::Pattern::Wildcard(_) => {}
+            ast::Pattern::Literal(literal) => {
+                // equal as with ==, values of other types never are
+                let literal = self._eval(literal, env);
+                let equal = self.eval_infix_expression("==", value.clone(), literal);
+                if equal.object_type() != "Bool" || !Eval::is_true(&*equal) {
+                    return Err(Eval::cannot_destructure(&value.inspect(), pattern));
+                }
+            }
+            ast::Pattern::Array { elements, rest, .. } => {
+                let array = match value.as_any().downcast_ref::<object::Array>() {
+                    Some(array) => array,
+                    None => return Err(Eval::cannot_destructure(value.object_type(), pattern)),
+                };
+                let len = array.elements.len();
+                if len < elements.len() || (rest.is_none() && len > elements.len()) {
+                    return Err(Eval::cannot_destructure(
+                        &format!("Array of length {}", len),
+                        pattern,
+                    ));
+                }
+                for (element, value) in elements.iter().zip(&array.elements) {
+                    self.destructure(element, value.clone(), env, bindings)?;
+                }
+                if let Some(rest) = rest {
+                    let elements = array.elements[elements.len()..].to_vec();
+                    bindings.push((rest.value.clone(), Box::new(object::Array { elements })));
+                }
+            }
+            ast::Pattern::Hash { pairs, .. } => {
+                let hash = match value.as_any().downcast_ref::<object::Hash>() {
+                    Some(hash) => hash,
+                    None => return Err(Eval::cannot_destructure(value.object_type(), pattern)),
+                };
+                for (key, element) in pairs {
+                    match hash.get(&object::HashKey::Str(key.clone())) {
+                        Some(value) => {
+                            self.destructure(element, value.clone_box(), env, bindings)?
+                        }
+                        None => {
+                            return Err(Eval::cannot_destructure(
+                                &format!("Hash without {:?}", key),
+                                pattern,
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // the handler runs when the body raises an error and the finalizer runs in any case,
+    // an error or a jump out of the finalizer replaces what the others resulted in
+    pub(super) fn eval_try_expression(
+        &self,
+        body: &Vec<ast::Statement>,
+        param: &Option<Box<ast::Pattern>>,
+        handler: &Option<Vec<ast::Statement>>,
+        finalizer: &Option<Vec<ast::Statement>>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        let mut rlt = self.eval_block(body, env);
+
+        if let (Some(handler), Some(err)) = (handler, rlt.as_any().downcast_ref::<object::Error>())
+        {
+            let caught = Eval::caught_value(err);
+            let handler_env = Rc::new(RefCell::new(Env::wrap_env(Rc::clone(env))));
+            let bound = match param {
+                Some(param) => self.bind_parameter(param, caught, &handler_env),
+                None => Ok(()),
+            };
+            rlt = match bound {
+                Ok(()) => self.eval_statements(handler, &handler_env),
+                Err(err) => err,
+            };
+        }
+
+        if let Some(finalizer) = finalizer {
+            let done = self.eval_block(finalizer, env);
+            if matches!(
+                done.object_type(),
+                "Error" | "ReturnValue" | "Break" | "Continue"
+            ) {
+                return done;
             }
         }
         rlt
     }
 
+    // a thrown value as it is, or a runtime error as {"message": .., "code": ..}
+    fn caught_value(err: &object::Error) -> Box<dyn object::Object> {
+        if let Some(thrown) = &err.thrown {
+            return thrown.clone();
+        }
+        let mut hash = object::Hash::new();
+        for (key, value) in [
+            ("message", err.value.clone()),
+            ("code", err.code.to_string()),
+        ] {
+            hash.insert(
+                object::HashKey::Str(key.to_string()),
+                Box::new(object::Str {
+                    value: key.to_string(),
+                }),
+                Box::new(object::Str { value }),
+            );
+        }
+        Box::new(hash)
+    }
+
+    // the first arm whose pattern fits the value and whose guard holds is taken,
+    // the names it binds are only seen in its guard and body
+    pub(super) fn eval_match_expression(
+        &self,
+        subject: &ast::Expression,
+        arms: &Vec<ast::MatchArm>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        let value = self._eval(subject, env);
+        if Eval::is_unwinding(&*value) {
+            return value;
+        }
+
+        for arm in arms {
+            let mut bindings = vec![];
+            if self
+                .destructure(&arm.pattern, value.clone(), env, &mut bindings)
+                .is_err()
+            {
+                continue;
+            }
+            let arm_env = Rc::new(RefCell::new(Env::wrap_env(Rc::clone(env))));
+            for (name, value) in bindings {
+                arm_env.borrow_mut().set(name, value);
+            }
+
+            if let Some(guard) = &arm.guard {
+                let guard = self._eval(guard, &arm_env);
+                if Eval::is_unwinding(&*guard) {
+                    return guard;
+                }
+                if !Eval::is_true(&*guard) {
+                    continue;
+                }
+            }
+            return self.eval_statements(&arm.body, &arm_env);
+        }
+
+        Eval::new_error(
+            error::NON_EXHAUSTIVE_MATCH,
+            format!("Non-exhaustive Match: no arm for {}", value.inspect()),
+        )
+    }
+
+    fn cannot_destructure(what: &str, pattern: &ast::Pattern) -> Box<dyn object::Object> {
+        Eval::new_error(
+            error::CANNOT_DESTRUCTURE,
+            format!("Cannot Destructure: {} into {}", what, pattern),
+        )
+    }
+
+    // names declared in a block are only seen inside of it, like let in JavaScript
+    pub(super) fn eval_block(
+        &self,
+        stmts: &Vec<ast::Statement>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        let block = Rc::new(RefCell::new(Env::wrap_env(Rc::clone(env))));
+        self.eval_statements(stmts, &block)
+    }
+
     pub(super) fn eval_expressions(
+        &self,
         expressions: &Vec<ast::Expression>,
         env: &Rc<RefCell<Env>>,
     ) -> Vec<Box<dyn object::Object>> {
         let mut v = vec![];
         for exp in expressions {
-            let e = Eval::_eval(exp, env);
-            if Eval::is_error(&e) {
+            let e = self._eval(exp, env);
+            if Eval::is_unwinding(&*e) {
                 return vec![e];
             }
             v.push(e);
@@ -61,15 +319,127 @@ impl Eval {
     }
 }
 
+// what a loop does after running its body once
+enum Flow {
+    Next,
+    Break,
+    Exit(Box<dyn object::Object>),
+}
+
+impl Eval {
+    pub(super) fn eval_while_statement(
+        &self,
+        label: &Option<String>,
+        condition: &ast::Expression,
+        body: &Vec<ast::Statement>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        loop {
+            let condition = self._eval(condition, env);
+            if Eval::is_unwinding(&*condition) {
+                return condition;
+            }
+            if !Eval::is_true(&*condition) {
+                break;
+            }
+            match Eval::loop_flow(self.eval_block(body, env), label) {
+                Flow::Next => {}
+                Flow::Break => break,
+                Flow::Exit(rlt) => return rlt,
+            }
+        }
+        Box::new(object::NULL)
+    }
+
+    pub(super) fn eval_for_statement(
+        &self,
+        label: &Option<String>,
+        init: &Option<Box<ast::Statement>>,
+        condition: &Option<ast::Expression>,
+        step: &Option<ast::Expression>,
+        body: &Vec<ast::Statement>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        // what init declares belongs to the loop, every iteration sees it
+        let env = &Rc::new(RefCell::new(Env::wrap_env(Rc::clone(env))));
+        if let Some(init) = init {
+            let init = self._eval(&**init, env);
+            if Eval::is_unwinding(&*init) {
+                return init;
+            }
+        }
+        loop {
+            if let Some(condition) = condition {
+                let condition = self._eval(condition, env);
+                if Eval::is_unwinding(&*condition) {
+                    return condition;
+                }
+                if !Eval::is_true(&*condition) {
+                    break;
+                }
+            }
+            match Eval::loop_flow(self.eval_block(body, env), label) {
+                Flow::Next => {}
+                Flow::Break => break,
+                Flow::Exit(rlt) => return rlt,
+            }
+            if let Some(step) = step {
+                let step = self._eval(step, env);
+                if Eval::is_unwinding(&*step) {
+                    return step;
+                }
+            }
+        }
+        Box::new(object::NULL)
+    }
+
+    // an unlabeled break or continue belongs to the innermost loop,
+    // a labeled one leaves every loop until the one with its label
+    fn loop_flow(rlt: Box<dyn object::Object>, label: &Option<String>) -> Flow {
+        let (target, is_break) = match rlt.object_type() {
+            "Break" => (
+                rlt.as_any()
+                    .downcast_ref::<object::Break>()
+                    .unwrap()
+                    .label
+                    .clone(),
+                true,
+            ),
+            "Continue" => (
+                rlt.as_any()
+                    .downcast_ref::<object::Continue>()
+                    .unwrap()
+                    .label
+                    .clone(),
+                false,
+            ),
+            "ReturnValue" | "Error" => return Flow::Exit(rlt),
+            _ => return Flow::Next,
+        };
+
+        if target.is_some() && target != *label {
+            Flow::Exit(rlt)
+        }
+        else if is_break {
+            Flow::Break
+        }
+        else {
+            Flow::Next
+        }
+    }
+}
+
 impl Eval {
     pub(super) fn eval_prefix_expression(
+        &self,
         operator: &str,
         right: Box<dyn object::Object>,
     ) -> Box<dyn object::Object> {
         match operator {
             "!" => Eval::eval_prefix_bang_expression(right),
-            "-" => Eval::eval_prefix_minus_expression(right),
-            _ => Eval::new_error("Never Occur".to_owned()),
+            "-" => self.eval_prefix_minus_expression(right),
+            "~" => Eval::eval_prefix_tilde_expression(right),
+            _ => Eval::new_error(error::UNKNOWN_OPERATOR, "Never Occur".to_owned()),
         }
     }
 
@@ -89,12 +459,21 @@ impl Eval {
                     .value;
                 object::static_bool_obj(right == 0)
             }
+            "Float" => {
+                let right = right
+                    .as_any()
+                    .downcast_ref::<object::Float>()
+                    .unwrap()
+                    .value;
+                object::static_bool_obj(right == 0.0)
+            }
             "Null" => object::TRUE,
             _ => object::FALSE,
         })
     }
 
     pub(super) fn eval_prefix_minus_expression(
+        &self,
         right: Box<dyn object::Object>,
     ) -> Box<dyn object::Object> {
         match right.object_type() {
@@ -104,28 +483,70 @@ impl Eval {
                     .downcast_ref::<object::Integer>()
                     .unwrap()
                     .value;
-                return Box::new(object::Integer { value: -right });
+                let value = match self.overflow {
+                    Overflow::Checked => right.checked_neg(),
+                    Overflow::Wrapping => Some(right.wrapping_neg()),
+                    Overflow::Saturating => Some(right.saturating_neg()),
+                };
+                match value {
+                    Some(value) => Box::new(object::Integer { value }),
+                    None => Eval::new_error(
+                        error::INTEGER_OVERFLOW,
+                        format!("Integer Overflow: -({})", right),
+                    ),
+                }
+            }
+            "Float" => {
+                let right = right
+                    .as_any()
+                    .downcast_ref::<object::Float>()
+                    .unwrap()
+                    .value;
+                Box::new(object::Float { value: -right })
             }
             _ => {
                 let s = format!("Unknown Operator: -{}", &right.object_type());
-                return Eval::new_error(s);
+                Eval::new_error(error::UNKNOWN_OPERATOR, s)
             }
-        };
+        }
+    }
+
+    pub(super) fn eval_prefix_tilde_expression(
+        right: Box<dyn object::Object>,
+    ) -> Box<dyn object::Object> {
+        match right.as_any().downcast_ref::<object::Integer>() {
+            Some(right) => Box::new(object::Integer {
+                value: !right.value,
+            }),
+            None => Eval::new_error(
+                error::UNKNOWN_OPERATOR,
+                format!("Unknown Operator: ~{}", &right.object_type()),
+            ),
+        }
     }
 
     pub(super) fn eval_infix_expression(
+        &self,
         operator: &str,
         left: Box<dyn object::Object>,
         right: Box<dyn object::Object>,
     ) -> Box<dyn object::Object> {
         match operator {
-            "+" | "-" | "*" | "/" | "<" | ">" => {
+            "+" | "-" | "*" | "/" | "%" | "**" | "<" | ">" | "<=" | ">=" => {
                 if left.object_type() == "Integer" && right.object_type() == "Integer" {
                     let &left = left.as_any().downcast_ref::<object::Integer>().unwrap();
-                    let left = Box::new(left);
                     let &right = right.as_any().downcast_ref::<object::Integer>().unwrap();
-                    let right = Box::new(right);
-                    Eval::eval_integer_infix_expression(operator, left, right)
+                    self.eval_integer_infix_expression(operator, left, right)
+                }
+                else if let (Some(left), Some(right)) =
+                    (Eval::as_float(&*left), Eval::as_float(&*right))
+                {
+                    Eval::eval_float_infix_expression(operator, left, right)
+                }
+                else if left.object_type() == "String" && right.object_type() == "String" {
+                    let left = left.as_any().downcast_ref::<object::Str>().unwrap();
+                    let right = right.as_any().downcast_ref::<object::Str>().unwrap();
+                    Eval::eval_string_infix_expression(operator, left, right)
                 }
                 else {
                     let s = format!(
@@ -134,22 +555,28 @@ impl Eval {
                         operator,
                         &right.object_type()
                     );
-                    Eval::new_error(s)
+                    Eval::new_error(error::TYPE_MISMATCH, s)
                 }
             }
             "==" | "!=" => {
                 if left.object_type() == "Integer" && right.object_type() == "Integer" {
                     let &left = left.as_any().downcast_ref::<object::Integer>().unwrap();
-                    let left = Box::new(left);
                     let &right = right.as_any().downcast_ref::<object::Integer>().unwrap();
-                    let right = Box::new(right);
-                    Eval::eval_integer_infix_expression(operator, left, right)
+                    self.eval_integer_infix_expression(operator, left, right)
+                }
+                else if let (Some(left), Some(right)) =
+                    (Eval::as_float(&*left), Eval::as_float(&*right))
+                {
+                    Eval::eval_float_infix_expression(operator, left, right)
+                }
+                else if left.object_type() == "String" && right.object_type() == "String" {
+                    let left = left.as_any().downcast_ref::<object::Str>().unwrap();
+                    let right = right.as_any().downcast_ref::<object::Str>().unwrap();
+                    Eval::eval_string_infix_expression(operator, left, right)
                 }
                 else if left.object_type() == "Bool" && right.object_type() == "Bool" {
                     let &left = left.as_any().downcast_ref::<object::Bool>().unwrap();
-                    let left = Box::new(left);
                     let &right = right.as_any().downcast_ref::<object::Bool>().unwrap();
-                    let right = Box::new(right);
                     Eval::eval_bool_infix_expression(operator, left, right)
                 }
                 else {
@@ -159,74 +586,596 @@ impl Eval {
                         operator,
                         &right.object_type()
                     );
-                    Eval::new_error(s)
+                    Eval::new_error(error::TYPE_MISMATCH, s)
+                }
+            }
+            "&" | "|" | "^" | "<<" | ">>" => {
+                if left.object_type() == "Integer" && right.object_type() == "Integer" {
+                    let &left = left.as_any().downcast_ref::<object::Integer>().unwrap();
+                    let &right = right.as_any().downcast_ref::<object::Integer>().unwrap();
+                    self.eval_integer_infix_expression(operator, left, right)
+                }
+                else {
+                    let (code, kind) = if left.object_type() == right.object_type() {
+                        (error::UNKNOWN_OPERATOR, "Unknown Operator")
+                    }
+                    else {
+                        (error::TYPE_MISMATCH, "Type Mismatched")
+                    };
+                    let s = format!(
+                        "{}: {} {} {}",
+                        kind,
+                        &left.object_type(),
+                        operator,
+                        &right.object_type()
+                    );
+                    Eval::new_error(code, s)
                 }
             }
-            _ => Eval::new_error("Never Occur".to_owned()),
+            _ => Eval::new_error(error::UNKNOWN_OPERATOR, "Never Occur".to_owned()),
         }
     }
 
     pub(super) fn eval_integer_infix_expression(
+        &self,
+        operator: &str,
+        left: object::Integer,
+        right: object::Integer,
+    ) -> Box<dyn object::Object> {
+        let (l, r) = (left.value, right.value);
+        let value = match operator {
+            "+" => self.arithmetic(
+                l,
+                r,
+                i64::checked_add,
+                i64::wrapping_add,
+                i64::saturating_add,
+            ),
+            "-" => self.arithmetic(
+                l,
+                r,
+                i64::checked_sub,
+                i64::wrapping_sub,
+                i64::saturating_sub,
+            ),
+            "*" => self.arithmetic(
+                l,
+                r,
+                i64::checked_mul,
+                i64::wrapping_mul,
+                i64::saturating_mul,
+            ),
+            "/" | "%" if r == 0 => {
+                return Eval::new_error(
+                    error::DIVISION_BY_ZERO,
+                    format!("Division by Zero: {} {} {}", l, operator, r),
+                )
+            }
+            "/" => self.arithmetic(
+                l,
+                r,
+                i64::checked_div,
+                i64::wrapping_div,
+                i64::saturating_div,
+            ),
+            // i64::MIN % -1 is 0, only the checked op reports it
+            "%" => self.arithmetic(l, r, i64::checked_rem, i64::wrapping_rem, i64::wrapping_rem),
+            "**" if r < 0 => {
+                return Eval::new_error(
+                    error::NEGATIVE_EXPONENT,
+                    format!("Negative Exponent: {} ** {}", l, r),
+                )
+            }
+            "**" => self.arithmetic(
+                l,
+                r,
+                |l, r| l.checked_pow(exponent(r)),
+                |l, r| l.wrapping_pow(exponent(r)),
+                |l, r| l.saturating_pow(exponent(r)),
+            ),
+            "<<" | ">>" if !(0..i64::BITS as i64).contains(&r) => {
+                return Eval::new_error(
+                    error::INVALID_SHIFT,
+                    format!("Invalid Shift: {} {} {}", l, operator, r),
+                )
+            }
+            "<<" => Some(l << r),
+            ">>" => Some(l >> r),
+            "&" => Some(l & r),
+            "|" => Some(l | r),
+            "^" => Some(l ^ r),
+            _ => {
+                return match operator {
+                    "<" => Box::new(object::static_bool_obj(l < r)),
+                    ">" => Box::new(object::static_bool_obj(l > r)),
+                    "<=" => Box::new(object::static_bool_obj(l <= r)),
+                    ">=" => Box::new(object::static_bool_obj(l >= r)),
+                    "==" => Box::new(object::static_bool_obj(l == r)),
+                    "!=" => Box::new(object::static_bool_obj(l != r)),
+                    _ => Eval::new_error(error::UNKNOWN_OPERATOR, "Never Occur".to_owned()),
+                };
+            }
+        };
+        match value {
+            Some(value) => Box::new(object::Integer { value }),
+            None => Eval::new_error(
+                error::INTEGER_OVERFLOW,
+                format!("Integer Overflow: {} {} {}", l, operator, r),
+            ),
+        }
+    }
+
+    // None only in the checked mode, when the result does not fit
+    fn arithmetic(
+        &self,
+        left: i64,
+        right: i64,
+        checked: fn(i64, i64) -> Option<i64>,
+        wrapping: fn(i64, i64) -> i64,
+        saturating: fn(i64, i64) -> i64,
+    ) -> Option<i64> {
+        match self.overflow {
+            Overflow::Checked => checked(left, right),
+            Overflow::Wrapping => Some(wrapping(left, right)),
+            Overflow::Saturating => Some(saturating(left, right)),
+        }
+    }
+
+    pub(super) fn eval_float_infix_expression(
         operator: &str,
-        left: Box<object::Integer>,
-        right: Box<object::Integer>,
+        left: f64,
+        right: f64,
     ) -> Box<dyn object::Object> {
         match operator {
-            "+" => Box::new(object::Integer {
-                value: left.value + right.value,
+            "+" => Box::new(object::Float {
+                value: left + right,
+            }),
+            "-" => Box::new(object::Float {
+                value: left - right,
             }),
-            "-" => Box::new(object::Integer {
-                value: left.value - right.value,
+            "*" => Box::new(object::Float {
+                value: left * right,
             }),
-            "*" => Box::new(object::Integer {
-                value: left.value * right.value,
+            "/" => Box::new(object::Float {
+                value: left / right,
             }),
-            "/" => Box::new(object::Integer {
-                value: left.value / right.value,
+            "%" => Box::new(object::Float {
+                value: left % right,
+            }),
+            "**" => Box::new(object::Float {
+                value: left.powf(right),
+            }),
+            "<" => Box::new(object::static_bool_obj(left < right)),
+            ">" => Box::new(object::static_bool_obj(left > right)),
+            "<=" => Box::new(object::static_bool_obj(left <= right)),
+            ">=" => Box::new(object::static_bool_obj(left >= right)),
+            "==" => Box::new(object::static_bool_obj(left == right)),
+            "!=" => Box::new(object::static_bool_obj(left != right)),
+            _ => Eval::new_error(error::UNKNOWN_OPERATOR, "Never Occur".to_owned()),
+        }
+    }
+
+    pub(super) fn eval_string_infix_expression(
+        operator: &str,
+        left: &object::Str,
+        right: &object::Str,
+    ) -> Box<dyn object::Object> {
+        match operator {
+            "+" => Box::new(object::Str {
+                value: format!("{}{}", left.value, right.value),
             }),
             "<" => Box::new(object::static_bool_obj(left.value < right.value)),
             ">" => Box::new(object::static_bool_obj(left.value > right.value)),
+            "<=" => Box::new(object::static_bool_obj(left.value <= right.value)),
+            ">=" => Box::new(object::static_bool_obj(left.value >= right.value)),
             "==" => Box::new(object::static_bool_obj(left.value == right.value)),
             "!=" => Box::new(object::static_bool_obj(left.value != right.value)),
-            _ => Eval::new_error("Never Occur".to_owned()),
+            _ => Eval::new_error(
+                error::UNKNOWN_OPERATOR,
+                format!("Unknown Operator: String {} String", operator),
+            ),
         }
     }
 
     pub(super) fn eval_bool_infix_expression(
         operator: &str,
-        left: Box<object::Bool>,
-        right: Box<object::Bool>,
+        left: object::Bool,
+        right: object::Bool,
     ) -> Box<dyn object::Object> {
         match operator {
             "==" => Box::new(object::static_bool_obj(left.value == right.value)),
             "!=" => Box::new(object::static_bool_obj(left.value != right.value)),
-            _ => Eval::new_error("Never Occur".to_owned()),
+            _ => Eval::new_error(error::UNKNOWN_OPERATOR, "Never Occur".to_owned()),
+        }
+    }
+
+    pub(super) fn eval_index_expression(
+        left: Box<dyn object::Object>,
+        index: Box<dyn object::Object>,
+    ) -> Box<dyn object::Object> {
+        if left.object_type() == "Array" && index.object_type() == "Integer" {
+            let array = left.as_any().downcast_ref::<object::Array>().unwrap();
+            let index = index.as_any().downcast_ref::<object::Integer>().unwrap();
+            Eval::eval_array_index_expression(array, index.value)
+        }
+        else if left.object_type() == "Hash" {
+            let hash = left.as_any().downcast_ref::<object::Hash>().unwrap();
+            let key = match index.hash_key() {
+                Some(key) => key,
+                None => return Eval::unusable_as_hash_key(&*index),
+            };
+            match hash.get(&key) {
+                Some(value) => value.clone_box(),
+                None => Box::new(object::NULL),
+            }
+        }
+        else {
+            Eval::new_error(
+                error::INDEX_NOT_SUPPORTED,
+                format!(
+                    "Index Operator not supported: {}[{}]",
+                    left.object_type(),
+                    index.object_type()
+                ),
+            )
+        }
+    }
+
+    pub(super) fn eval_hash_literal(
+        &self,
+        pairs: &Vec<(ast::Expression, ast::Expression)>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        let mut hash = object::Hash::new();
+        for (key, value) in pairs {
+            let key = self._eval(key, env);
+            if Eval::is_unwinding(&*key) {
+                return key;
+            }
+            let hash_key = match key.hash_key() {
+                Some(hash_key) => hash_key,
+                None => return Eval::unusable_as_hash_key(&*key),
+            };
+
+            let value = self._eval(value, env);
+            if Eval::is_unwinding(&*value) {
+                return value;
+            }
+            hash.insert(hash_key, key, value);
+        }
+        Box::new(hash)
+    }
+
+    fn unusable_as_hash_key(key: &dyn object::Object) -> Box<dyn object::Object> {
+        Eval::new_error(
+            error::UNUSABLE_AS_HASH_KEY,
+            format!("Unusable as hash key: {}", key.object_type()),
+        )
+    }
+
+    // a negative index counts from the end, -1 is the last element
+    pub(super) fn eval_array_index_expression(
+        array: &object::Array,
+        index: i64,
+    ) -> Box<dyn object::Object> {
+        match Eval::array_position(array, index) {
+            Ok(position) => array.elements[position].clone(),
+            Err(e) => e,
+        }
+    }
+
+    fn array_position(array: &object::Array, index: i64) -> Result<usize, Box<dyn object::Object>> {
+        let len = array.elements.len() as i64;
+        let i = if index < 0 { len + index } else { index };
+        if i < 0 || i >= len {
+            return Err(Eval::new_error(
+                error::INDEX_OUT_OF_BOUNDS,
+                format!(
+                    "Index out of bounds: index {} for Array of length {}",
+                    index, len
+                ),
+            ));
+        }
+        Ok(i as usize)
+    }
+
+    // stores value into the place and returns what was stored,
+    // a compound operator like += combines it with the current value first.
+    // a variable is kept by the nearest scope which declared it, so a closure updates
+    // the one it captured. an element is written into a copy of its container,
+    // and the copies are stored back along the path up to the variable
+    pub(super) fn assign_place(
+        &self,
+        target: &ast::Expression,
+        operator: &str,
+        value: Box<dyn object::Object>,
+        env: &Rc<RefCell<Env>>,
+    ) -> Box<dyn object::Object> {
+        match target {
+            ast::Expression::Ident(ast::Identifier { value: name, .. }) => {
+                let value = match compound_operator(operator) {
+                    Some(op) => {
+                        let current = Eval::eval_ident(name, env);
+                        if Eval::is_unwinding(&*current) {
+                            return current;
+                        }
+                        self.eval_infix_expression(op, current, value)
+                    }
+                    None => value,
+                };
+                if Eval::is_unwinding(&*value) {
+                    return value;
+                }
+                let assigned = env.borrow_mut().assign(name, value.clone());
+                match assigned {
+                    Ok(()) => value,
+                    Err(EnvError::Constant) => Eval::new_error(
+                        error::ASSIGN_TO_CONST,
+                        format!("Cannot Assign to const: {}", name),
+                    ),
+                    Err(EnvError::Undeclared) => Eval::new_error(
+                        error::INVALID_ASSIGN,
+                        format!("Cannot Assign to undeclared Ident: {}", name),
+                    ),
+                }
+            }
+            ast::Expression::Index { .. } => {
+                // a[i][j] is the root a with the keys i and j
+                let mut keys = vec![];
+                let mut root = target;
+                while let ast::Expression::Index { left, index, .. } = root {
+                    keys.push(&**index);
+                    root = &**left;
+                }
+
+                // every key is evaluated once, the containers met on the way are kept
+                let mut path = vec![];
+                let mut current = self._eval(root, env);
+                for key in keys.into_iter().rev() {
+                    if Eval::is_unwinding(&*current) {
+                        return current;
+                    }
+                    let key = self._eval(key, env);
+                    if Eval::is_unwinding(&*key) {
+                        return key;
+                    }
+                    let element = Eval::eval_index_expression(current.clone(), key.clone());
+                    path.push((current, key));
+                    current = element;
+                }
+
+                let value = match compound_operator(operator) {
+                    Some(op) => {
+                        if Eval::is_unwinding(&*current) {
+                            return current;
+                        }
+                        self.eval_infix_expression(op, current, value)
+                    }
+                    None => value,
+                };
+                if Eval::is_unwinding(&*value) {
+                    return value;
+                }
+
+                let mut stored = value.clone();
+                for (container, key) in path.into_iter().rev() {
+                    stored = Eval::eval_index_assignment(container, key, stored);
+                    if Eval::is_unwinding(&*stored) {
+                        return stored;
+                    }
+                }
+                let rlt = self.assign_place(root, "=", stored, env);
+                if Eval::is_unwinding(&*rlt) {
+                    return rlt;
+                }
+                value
+            }
+            _ => Eval::new_error(
+                error::INVALID_ASSIGN,
+                format!("Cannot Assign to {}", target),
+            ),
+        }
+    }
+
+    // the container with the element at index replaced, or added for a new hash key
+    pub(super) fn eval_index_assignment(
+        container: Box<dyn object::Object>,
+        index: Box<dyn object::Object>,
+        value: Box<dyn object::Object>,
+    ) -> Box<dyn object::Object> {
+        if container.object_type() == "Array" && index.object_type() == "Integer" {
+            let mut array = container
+                .as_any()
+                .downcast_ref::<object::Array>()
+                .unwrap()
+                .clone();
+            let index = index.as_any().downcast_ref::<object::Integer>().unwrap();
+            match Eval::array_position(&array, index.value) {
+                Ok(position) => array.elements[position] = value,
+                Err(e) => return e,
+            }
+            Box::new(array)
+        }
+        else if container.object_type() == "Hash" {
+            let mut hash = container
+                .as_any()
+                .downcast_ref::<object::Hash>()
+                .unwrap()
+                .clone();
+            match index.hash_key() {
+                Some(key) => hash.insert(key, index, value),
+                None => return Eval::unusable_as_hash_key(&*index),
+            }
+            Box::new(hash)
+        }
+        else {
+            Eval::new_error(
+                error::INDEX_NOT_SUPPORTED,
+                format!(
+                    "Index Assignment not supported: {}[{}]",
+                    container.object_type(),
+                    index.object_type()
+                ),
+            )
         }
     }
 
     pub(super) fn eval_ident(name: &String, env: &Rc<RefCell<Env>>) -> Box<dyn object::Object> {
         match env.borrow().get(name) {
             Some(v) => v.clone(),
-            None => Eval::new_error(format!("Ident not found: {}", name)),
+            None => match Eval::look_up_builtin(name) {
+                Some(builtin) => builtin,
+                None => {
+                    Eval::new_error(error::IDENT_NOT_FOUND, format!("Ident not found: {}", name))
+                }
+            },
         }
     }
 
     pub(super) fn put_args_in_function(
+        &self,
         func: Box<object::Function>,
         args: Vec<Box<dyn object::Object>>,
+        named: Vec<(String, Box<dyn object::Object>)>,
     ) -> Box<dyn object::Object> {
-        let closure = Eval::make_func_env(&func, args);
-        Eval::eval_program(&func.body, &closure)
+        match self.make_func_env(&func, args, named) {
+            Ok(closure) => {
+                self.defers.borrow_mut().push(vec![]);
+                let rlt = self.eval_program(&func.body, &closure);
+                self.run_deferred(rlt)
+            }
+            // a ? in a default returns from the function being called
+            Err(err) => match err.as_any().downcast_ref::<object::ReturnValue>() {
+                Some(rlt) => rlt.value.clone(),
+                None => err,
+            },
+        }
     }
 
+    // what a call deferred runs last in first out, however the call ended,
+    // an error from one of them replaces the result of the call
+    fn run_deferred(&self, mut rlt: Box<dyn object::Object>) -> Box<dyn object::Object> {
+        let deferred = self.defers.borrow_mut().pop().unwrap_or_default();
+        for (expression, env) in deferred.iter().rev() {
+            let done = self._eval(expression, env);
+            if Eval::is_error(&*done) {
+                rlt = done;
+            }
+        }
+        rlt
+    }
+
+    // positional arguments are bound in order and named ones by name, the left ones get their
+    // defaults, evaluated in the new env so they can use earlier parameters.
+    // extra positional arguments are collected into the rest parameter
     fn make_func_env(
-        func: &Box<object::Function>,
+        &self,
+        func: &object::Function,
         args: Vec<Box<dyn object::Object>>,
-    ) -> Rc<RefCell<Env>> {
-        let mut closure = Env::wrap_env(func.env.clone()); // it is also 'clone'
-        for (i, param) in func.parameters.iter().enumerate() {
-            closure.set(param.value.clone(), args[i].clone())
+        named: Vec<(String, Box<dyn object::Object>)>,
+    ) -> Result<Rc<RefCell<Env>>, Box<dyn object::Object>> {
+        let name = func.name.as_deref().unwrap_or("fn");
+        let (rest, params) = match func.parameters.split_last() {
+            Some((last, params)) if last.rest => (Some(last), params),
+            _ => (None, &func.parameters[..]),
+        };
+        let given = args.len() + named.len();
+        let wrong_number = || {
+            let required = params.iter().filter(|p| p.default.is_none()).count();
+            let expected = match rest {
+                Some(_) => format!("at least {}", required),
+                None if required == params.len() => required.to_string(),
+                None => format!("{} to {}", required, params.len()),
+            };
+            Eval::new_error(
+                error::WRONG_ARGUMENTS,
+                format!(
+                    "Wrong number of Arguments: {} expected {}, got {}",
+                    name, expected, given
+                ),
+            )
+        };
+
+        let mut args = args.into_iter();
+        let mut bound: Vec<Option<Box<dyn object::Object>>> =
+            params.iter().map(|_| args.next()).collect();
+        let extra: Vec<Box<dyn object::Object>> = args.collect();
+        if !extra.is_empty() && rest.is_none() {
+            return Err(wrong_number());
         }
-        Rc::new(RefCell::new(closure))
+
+        let has_named = !named.is_empty();
+        for (key, value) in named {
+            let position = params
+                .iter()
+                .position(|p| matches!(&p.pattern, ast::Pattern::Ident(i) if i.value == key));
+            match position {
+                Some(i) if bound[i].is_some() => {
+                    return Err(Eval::new_error(
+                        error::WRONG_ARGUMENTS,
+                        format!("Argument given twice: {} of {}", key, name),
+                    ))
+                }
+                Some(i) => bound[i] = Some(value),
+                None => {
+                    return Err(Eval::new_error(
+                        error::WRONG_ARGUMENTS,
+                        format!("Unknown Argument: {} of {}", key, name),
+                    ))
+                }
+            }
+        }
+
+        let closure = Rc::new(RefCell::new(Env::wrap_env(func.env.clone()))); // it is also 'clone'
+        for (param, value) in params.iter().zip(bound) {
+            let value = match (value, &param.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => {
+                    let value = self._eval(default, &closure);
+                    if Eval::is_unwinding(&*value) {
+                        return Err(value);
+                    }
+                    value
+                }
+                // counting only makes sense when everything was given by position
+                (None, None) if has_named => {
+                    return Err(Eval::new_error(
+                        error::WRONG_ARGUMENTS,
+                        format!("Missing Argument: {} of {}", param.pattern, name),
+                    ))
+                }
+                (None, None) => return Err(wrong_number()),
+            };
+            self.bind_parameter(&param.pattern, value, &closure)?;
+        }
+        if let Some(rest) = rest {
+            let elements = Box::new(object::Array { elements: extra });
+            self.bind_parameter(&rest.pattern, elements, &closure)?;
+        }
+        Ok(closure)
     }
+
+    fn bind_parameter(
+        &self,
+        pattern: &ast::Pattern,
+        value: Box<dyn object::Object>,
+        closure: &Rc<RefCell<Env>>,
+    ) -> Result<(), Box<dyn object::Object>> {
+        let mut bindings = vec![];
+        self.destructure(pattern, value, closure, &mut bindings)?;
+        for (key, value) in bindings {
+            closure.borrow_mut().set(key, value);
+        }
+        Ok(())
+    }
+}
+
+// "+" for "+=", None for a plain "="
+fn compound_operator(operator: &str) -> Option<&str> {
+    operator.strip_suffix('=').filter(|op| !op.is_empty())
+}
+
+// exponents beyond u32 only matter for 0, 1 and -1, so keep the parity for -1
+fn exponent(r: i64) -> u32 {
+    u32::try_from(r).unwrap_or(u32::MAX - 1 + (r % 2) as u32)
 }
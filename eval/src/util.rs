@@ -1,7 +1,7 @@
 use super::Eval;
 
 impl Eval {
-    pub(super) fn is_true(obj: &Box<dyn object::Object>) -> bool {
+    pub(super) fn is_true(obj: &dyn object::Object) -> bool {
         match obj.object_type() {
             "Bool" => *obj.as_any().downcast_ref::<object::Bool>().unwrap().value,
             "Integer" => {
@@ -13,19 +13,60 @@ impl Eval {
 
                 int_val != 0
             }
+            "Float" => obj.as_any().downcast_ref::<object::Float>().unwrap().value != 0.0,
             "Null" => false,
             _ => true,
         }
     }
 
-    pub(super) fn is_error(obj: &Box<dyn object::Object>) -> bool {
+    // a Float, or an Integer taking part in arithmetic with one
+    pub(super) fn as_float(obj: &dyn object::Object) -> Option<f64> {
+        if let Some(f) = obj.as_any().downcast_ref::<object::Float>() {
+            Some(f.value)
+        }
+        else {
+            obj.as_any()
+                .downcast_ref::<object::Integer>()
+                .map(|i| i.value as f64)
+        }
+    }
+
+    pub(super) fn is_error(obj: &dyn object::Object) -> bool {
         obj.object_type() == "Error"
     }
 
+    // an error, a value on its way out of its function by return or ?,
+    // or a break / continue on its way to its loop stops whatever it is in
+    pub(super) fn is_unwinding(obj: &dyn object::Object) -> bool {
+        matches!(
+            obj.object_type(),
+            "Error" | "ReturnValue" | "Break" | "Continue"
+        )
+    }
+
     // issue #20
     // formatted string & variable argument using macro
     // format_argument! may helpful
-    pub(super) fn new_error(value: String) -> Box<object::Error> {
-        Box::new(object::Error { value })
+    pub(super) fn new_error(code: &'static str, value: String) -> Box<object::Error> {
+        Box::new(object::Error {
+            value,
+            code,
+            span: None,
+            thrown: None,
+        })
+    }
+
+    // an error is located at the innermost expression raising it
+    pub(super) fn with_span(
+        obj: Box<dyn object::Object>,
+        span: ast::Span,
+    ) -> Box<dyn object::Object> {
+        match obj.as_any().downcast_ref::<object::Error>() {
+            Some(err) if err.span.is_none() => Box::new(object::Error {
+                span: Some(span),
+                ..err.clone()
+            }),
+            _ => obj,
+        }
     }
 }
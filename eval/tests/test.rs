@@ -357,3 +357,967 @@
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod eval_tests {
+    use eval::{Eval, Overflow};
+
+    #[test]
+    fn test_integer() {
+        let inputs: Vec<&str> = vec![
+            "5;",
+            "123;",
+            "0;",
+            "1_000 + 0x10 + 0o10 + 0b10;",
+            "99999999999999999999;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "5",
+            "123",
+            "0",
+            "1026",
+            "PAR0006: Integer literal 99999999999999999999 is out of range for i64 (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_prefix_bang_expression() {
+        let inputs: Vec<&str> = vec![
+            "!5;", "!0;", "!!123;", "!true;", "!false;", "!!true;", "!!false;",
+        ];
+
+        let expected: Vec<&str> = vec!["false", "true", "true", "false", "true", "true", "false"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_integer_infix_expression() {
+        let inputs: Vec<&str> = vec![
+            "5 + 5 + 5 - 10;",
+            "2 * 2 * 2 * 2 * 2;",
+            "-50 + 100 + -50;",
+            "5 * 2 + 10;",
+            "5 + 2 * 10;",
+            "(1 + 2) * 3 - 2 - (3 + 4) * 1;",
+            "1 < 2;",
+            "2 > 2;",
+            "1 == 1;",
+            "1 != 1;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "5", "32", "0", "20", "25", "0", "true", "false", "true", "false",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        let inputs: Vec<&str> = vec![
+            "1 / 0;",
+            "9223372036854775807 + 1;",
+            "-9223372036854775808 - 1;",
+            "4611686018427387904 * 2;",
+            "-9223372036854775808 / -1;",
+            "let x = -9223372036854775808; -x;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "Division by Zero: 1 / 0 (line 1, column 1)",
+            "Integer Overflow: 9223372036854775807 + 1 (line 1, column 1)",
+            "Integer Overflow: -9223372036854775808 - 1 (line 1, column 1)",
+            "Integer Overflow: 4611686018427387904 * 2 (line 1, column 1)",
+            "Integer Overflow: -9223372036854775808 / -1 (line 1, column 1)",
+            "Integer Overflow: -(-9223372036854775808) (line 1, column 31)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+
+        let inputs: Vec<&str> = vec![
+            "9223372036854775807 + 1;",
+            "-9223372036854775808 / -1;",
+            "1 / 0;",
+        ];
+
+        let wrapping: Vec<&str> = vec![
+            "-9223372036854775808",
+            "-9223372036854775808",
+            "Division by Zero: 1 / 0 (line 1, column 1)",
+        ];
+        let saturating: Vec<&str> = vec![
+            "9223372036854775807",
+            "9223372036854775807",
+            "Division by Zero: 1 / 0 (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::with_overflow(Overflow::Wrapping);
+            assert_eq!(e.run(input.to_string()), wrapping[i]);
+            let e = Eval::with_overflow(Overflow::Saturating);
+            assert_eq!(e.run(input.to_string()), saturating[i]);
+        }
+    }
+
+    #[test]
+    fn test_operators() {
+        let inputs: Vec<&str> = vec![
+            "2 <= 2;",
+            "3 >= 4;",
+            "1.5 <= 1;",
+            "\"a\" <= \"b\";",
+            "7 % 3;",
+            "-7 % 3;",
+            "7.5 % 2;",
+            "2 ** 10;",
+            "2 ** 3 ** 2;",
+            "2 ** 0.5 > 1.41;",
+            "2 ** 63;",
+            "2 ** -1;",
+            "1 % 0;",
+            "false && undefined;",
+            "true || undefined;",
+            "true && 1 < 2;",
+            "false || false;",
+            "12 & 10;",
+            "12 | 10;",
+            "12 ^ 10;",
+            "~0;",
+            "1 << 4;",
+            "-16 >> 2;",
+            "1 << 64;",
+            "1.5 & 1;",
+            "true | false;",
+            "~true;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "true",
+            "false",
+            "false",
+            "true",
+            "1",
+            "-1",
+            "1.5",
+            "1024",
+            "512",
+            "true",
+            "Integer Overflow: 2 ** 63 (line 1, column 1)",
+            "Negative Exponent: 2 ** -1 (line 1, column 1)",
+            "Division by Zero: 1 % 0 (line 1, column 1)",
+            "false",
+            "true",
+            "true",
+            "false",
+            "8",
+            "14",
+            "6",
+            "-1",
+            "16",
+            "-4",
+            "Invalid Shift: 1 << 64 (line 1, column 1)",
+            "Type Mismatched: Float & Integer (line 1, column 1)",
+            "Unknown Operator: Bool | Bool (line 1, column 1)",
+            "Unknown Operator: ~Bool (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_float() {
+        let inputs: Vec<&str> = vec![
+            "1.5;",
+            "2e3;",
+            "0.1 + 0.2;",
+            "1.5 * 2;",
+            "3 / 2.0;",
+            "let xs = [1, 2, 4]; (xs[0] + xs[1] + xs[2]) / 3.0;",
+            "-2.5;",
+            "1 < 1.5;",
+            "2.0 == 2;",
+            "2.5 != 2.5;",
+            "!0.0;",
+            "if (0.5) { 1 } else { 2 };",
+            "1.0 / 0;",
+            "1.5 + true;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "1.5",
+            "2000.0",
+            "0.30000000000000004",
+            "3.0",
+            "1.5",
+            "2.3333333333333335",
+            "-2.5",
+            "true",
+            "true",
+            "false",
+            "true",
+            "1",
+            "inf",
+            "Type Mismatched: Float + Bool (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_string() {
+        let inputs: Vec<&str> = vec![
+            "\"hello\";",
+            "\"hello\" + \" \" + \"world\";",
+            "let greet = fn(name) { \"hi, \" + name }; greet(\"squirt\");",
+            "\"a\" == \"a\";",
+            "\"a\" != \"a\";",
+            "\"abc\" < \"abd\";",
+            "\"b\" > \"abc\";",
+            "\"line\\n\";",
+            "\"a\" - \"b\";",
+            "\"a\" + 1;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "\"hello\"",
+            "\"hello world\"",
+            "\"hi, squirt\"",
+            "true",
+            "false",
+            "true",
+            "true",
+            "\"line\\n\"",
+            "Unknown Operator: String - String (line 1, column 1)",
+            "Type Mismatched: String + Integer (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_array() {
+        let inputs: Vec<&str> = vec![
+            "[1, 2 * 2, \"three\", [true]];",
+            "[];",
+            "[1, 2, 3][0];",
+            "[1, 2, 3][1 + 1];",
+            "let arr = [1, 2, 3]; arr[0] + arr[1] + arr[2];",
+            "let arr = [1, 2, 3]; arr[-1];",
+            "let arr = [1, 2, 3]; arr[-3];",
+            "[[1, 2], [3, 4]][1][0];",
+            "[1, 2, 3][3];",
+            "[1, 2, 3][-4];",
+            "[][0];",
+            "[1][true];",
+            "1[0];",
+            "[1, foo];",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "[1, 4, \"three\", [true]]",
+            "[]",
+            "1",
+            "3",
+            "6",
+            "3",
+            "1",
+            "3",
+            "Index out of bounds: index 3 for Array of length 3 (line 1, column 1)",
+            "Index out of bounds: index -4 for Array of length 3 (line 1, column 1)",
+            "Index out of bounds: index 0 for Array of length 0 (line 1, column 1)",
+            "Index Operator not supported: Array[Bool] (line 1, column 1)",
+            "Index Operator not supported: Integer[Integer] (line 1, column 1)",
+            "Ident not found: foo (line 1, column 5)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        let inputs: Vec<&str> = vec![
+            "{\"b\": 1, \"a\": 2, 3: true, false: [1]};",
+            "{};",
+            "let h = {\"one\": 1, \"two\": 2}; h[\"one\"] + h[\"two\"];",
+            "let key = \"k\"; {key: 5}[\"k\"];",
+            "{1: \"int\", true: \"bool\"}[1];",
+            "{1: \"int\", true: \"bool\"}[true];",
+            "{\"a\": 1, \"b\": 2, \"a\": 3};",
+            "{\"a\": 1}[\"b\"];",
+            "{fn(x) { x }: 1};",
+            "{\"a\": 1}[[1]];",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "{\"b\": 1, \"a\": 2, 3: true, false: [1]}",
+            "{}",
+            "3",
+            "5",
+            "\"int\"",
+            "\"bool\"",
+            "{\"a\": 3, \"b\": 2}",
+            "null",
+            "Unusable as hash key: Function (line 1, column 1)",
+            "Unusable as hash key: Array (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_if_expression() {
+        let inputs: Vec<&str> = vec![
+            "if(true) { 10; }",
+            "if(false) { 10; }",
+            "if(true) { 10; } else { 20; }",
+            "if(false) { 10; } else { 20; }",
+            "if(0) { 10; } else { 20; }",
+            "if(1) { 10; } else { 20; }",
+            "let x = 5; if (x < 0) { 1 } else if (x < 10) { 2 } else { 3 }",
+            "let x = 50; if x < 0 { 1 } else if x < 10 { 2 } else { 3 }",
+            "let x = -1; if x < 0 { 1 } else if x < 10 { 2 } else { 3 }",
+            "let x = 50; if x < 0 { 1 } else if x < 10 { 2 }",
+        ];
+
+        let expected: Vec<&str> = vec!["10", "null", "10", "20", "20", "10", "2", "3", "1", "null"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_return() {
+        let input = "
+            1;
+            if(true) {
+                if(false) {
+                    return 2;
+                }
+                3;
+                if(true) {
+                    return 4;
+                }
+                return 5;
+            }
+            return 6;
+        "
+        .to_string();
+
+        let e = Eval::new();
+        assert_eq!(e.run(input), "4");
+    }
+
+    #[test]
+    fn test_loop() {
+        let inputs: Vec<&str> = vec![
+            "let i = 0; while (i < 10) { i = i + 1; } i;",
+            "let s = 0; for (let i = 0; i < 5; i = i + 1) { s = s + i; } s;",
+            "let i = 0; while (true) { i = i + 1; if (i == 3) { break; } } i;",
+            "let s = 0; for (let i = 0; i < 5; i = i + 1) { if (i % 2 == 0) { continue; } s = s + i; } s;",
+            "let n = 0;
+             outer: for (let i = 0; i < 3; i = i + 1) {
+                 for (let j = 0; j < 3; j = j + 1) {
+                     if (j == 1) { continue outer; }
+                     if (i == 2) { break outer; }
+                     n = n + 1;
+                 }
+             }
+             n;",
+            "let f = fn() { let i = 0; while (true) { i = i + 1; if (i == 5) { return i; } } }; f();",
+            "let i = 0; while (i < 100000) { i = i + 1; } i;",
+            "let x = 0; while (true) { x += 1; let y = if (x > 3) { break; } else { 1 }; } x;",
+            "let s = 0; for (let i = 0; i < 5; i += 1) { s = s + (if (i == 3) { continue; } else { i }); } s;",
+            "let n = 0; while (true) { n += 1; const c = [n, if (n == 2) { break; } else { n }]; } n;",
+            "let xs = [1, 2, 3]; let t = 0; for (let i = 0; i < 3; i += 1) { t += xs[if (i == 1) { continue; } else { i }]; } t;",
+            "let f = fn(a) { a }; let i = 0; while (true) { i += 1; f({\"k\": if (i == 2) { break; } else { i }}); } i;",
+            "let i = 0; while (true) { i += 1; let y = { if (i == 3) { break; } i }; } i;",
+            "while (x) { }",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "10",
+            "10",
+            "3",
+            "4",
+            "2",
+            "5",
+            "100000",
+            "4",
+            "7",
+            "2",
+            "4",
+            "2",
+            "3",
+            "Ident not found: x (line 1, column 8)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_binding() {
+        let inputs: Vec<&str> = vec![
+            "let a = 5; a;",
+            "let a = 5 * 5; a;",
+            "let a = 1; let b = a; b;",
+            "let add = fn(x, y) { x + y }; add(1, add(2, 3));",
+            "let closure = fn(x) { fn(y) { x + y } }; let inner = closure(10); inner(5);",
+            "let five = fn() { 5 }; five() + 1;",
+            "// comment\nlet a = 4; /* a is /* nested */ 4 */ a / 2; // end",
+            "let 변수 = 1; let x1 = 2; 변수 + x1;",
+        ];
+
+        let expected: Vec<&str> = vec!["5", "25", "1", "6", "15", "6", "2", "3"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_assign() {
+        let inputs: Vec<&str> = vec![
+            "let a = 1; a = 2; a;",
+            "let a = 1; let b = 2; a = b = 3; a + b;",
+            "let a = 1; a = 5;",
+            "let counter = fn() { let n = 0; fn() { n = n + 1; n } };
+             let next = counter();
+             next(); next(); next();",
+            "let total = 0; let add = fn(x) { total = total + x; }; add(3); add(4); total;",
+            "let x = 1; let f = fn(x) { x = 10; x }; f(2) + x;",
+            "y = 1;",
+            "let a = 1; 2 = a;",
+            "let a = 10; a += 5; a -= 3; a *= 2; a /= 4; a %= 4;",
+            "let s = \"a\"; s += \"b\";",
+            "let xs = [1, 2, 3]; xs[0] = 10; xs[-1] += 5; xs;",
+            "let h = {\"a\": 1}; h[\"b\"] = 2; h[\"a\"] *= 10; h;",
+            "let m = [[1, 2], [3, 4]]; m[1][0] = 30; m;",
+            "let h = {\"xs\": [1]}; h[\"xs\"][0] += 1; h[\"xs\"];",
+            "let a = [1]; let b = a; a[0] = 2; b;",
+            "let n = 0; let f = fn() { n += 1; n - 1 };
+             let a = [[1, 2], [3, 4]]; a[f()][0] = 9; [n, a];",
+            "let n = 0; let f = fn() { n += 1; n - 1 };
+             let a = [[1, 2], [3, 4]]; a[f()][0] += 5; [n, a];",
+            "let xs = [1]; let push = fn() { xs[0] = 9; }; push(); xs;",
+            "let xs = [1]; xs[1] = 2;",
+            "let h = {}; h[[1]] = 2;",
+            "let s = \"abc\"; s[0] = 1;",
+            "let a = 1; a += true;",
+            "z += 1;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "2",
+            "6",
+            "5",
+            "3",
+            "7",
+            "11",
+            "Cannot Assign to undeclared Ident: y (line 1, column 1)",
+            "PAR0011: Cannot assign to 2 (line 1, column 14)",
+            "2",
+            "\"ab\"",
+            "[10, 2, 8]",
+            "{\"a\": 10, \"b\": 2}",
+            "[[1, 2], [30, 4]]",
+            "[2]",
+            "[1]",
+            "[1, [[9, 2], [3, 4]]]",
+            "[1, [[6, 2], [3, 4]]]",
+            "[9]",
+            "Index out of bounds: index 1 for Array of length 1 (line 1, column 15)",
+            "Unusable as hash key: Array (line 1, column 13)",
+            "Index Assignment not supported: String[Integer] (line 1, column 16)",
+            "Type Mismatched: Integer + Bool (line 1, column 12)",
+            "Ident not found: z (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_block_scope() {
+        let inputs: Vec<&str> = vec![
+            "if (true) { let x = 1; } x;",
+            "let x = 1; if (true) { let x = 2; } x;",
+            "let x = 1; if (true) { x = 2; } x;",
+            "let x = 1; if (false) { 0 } else { let x = 3; x };",
+            "{ let y = 1; y + 1 };",
+            "{ let y = 1; } y;",
+            "let x = 1; { let x = 2; x = 3; } x;",
+            "for (let i = 0; i < 3; i += 1) { } i;",
+            "let i = 10; for (let i = 0; i < 3; i += 1) { } i;",
+            "let n = 0; while (n < 3) { let n2 = n * 2; n += 1; } n2;",
+            "let f = fn() { { return 5; } 1 }; f();",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "Ident not found: x (line 1, column 26)",
+            "1",
+            "2",
+            "3",
+            "2",
+            "Ident not found: y (line 1, column 16)",
+            "1",
+            "Ident not found: i (line 1, column 36)",
+            "10",
+            "Ident not found: n2 (line 1, column 54)",
+            "5",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_const() {
+        let inputs: Vec<&str> = vec![
+            "const x = 5; x * 2;",
+            "const x = 1; { let x = 2; x = 3; x };",
+            "const x = 1; let f = fn(x) { x = 2; x }; f(0);",
+            "const x = 1; const x = 2;",
+            "const x = 1; let x = 2;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "10",
+            "3",
+            "2",
+            "Cannot Redeclare const: x (line 1, column 14)",
+            "Cannot Redeclare const: x (line 1, column 14)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+
+        // each line is parsed alone like in the REPL, so only the evaluator can tell
+        let e = Eval::new();
+        assert_eq!(e.run("const limit = 3;".to_string()), "null");
+        assert_eq!(
+            e.run("limit = 4;".to_string()),
+            "Cannot Assign to const: limit (line 1, column 1)"
+        );
+        assert_eq!(
+            e.run("let bump = fn() { limit += 1 }; bump();".to_string()),
+            "Cannot Assign to const: limit (line 1, column 19)"
+        );
+        assert_eq!(e.run("limit;".to_string()), "3");
+    }
+
+    #[test]
+    fn test_function_statement() {
+        let inputs: Vec<&str> = vec![
+            "fn add(x, y) { x + y } add(1, 2);",
+            "let r = double(4); fn double(x) { x * 2 } r;",
+            "fn is_even(n) { if (n == 0) { true } else { is_odd(n - 1) } }
+             fn is_odd(n) { if (n == 0) { false } else { is_even(n - 1) } }
+             is_odd(7);",
+            "fn fact(n) { if (n < 2) { 1 } else { n * fact(n - 1) } } fact(10);",
+            "fn outer() { return inner() + 1; fn inner() { 41 } } outer();",
+            "{ fn hidden() { 1 } } hidden();",
+            "fn add(x, y) { x + y } add;",
+            "let f = fn(x) { x }; f;",
+            "let f = fn(x) { let y = x; y }; f;",
+            "fn add(x, y) { x + y } add(1);",
+            "fn(x) { x }();",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "3",
+            "8",
+            "true",
+            "3628800",
+            "42",
+            "Ident not found: hidden (line 1, column 23)",
+            "fn add(x, y) { (x + y) }",
+            "fn(x) { x }",
+            "fn(x) { let y = x; y }",
+            "Wrong number of Arguments: add expected 2, got 1 (line 1, column 24)",
+            "Wrong number of Arguments: fn expected 1, got 0 (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+
+        let e = Eval::new();
+        assert_eq!(e.run("const f = 1;".to_string()), "null");
+        assert_eq!(
+            e.run("fn f() { }".to_string()),
+            "Cannot Redeclare const: f (line 1, column 1)"
+        );
+    }
+
+    #[test]
+    fn test_arrow_function() {
+        let inputs: Vec<&str> = vec![
+            "let inc = x => x + 1; inc(41);",
+            "let mul = (x, y) => x * y; mul(6, 7);",
+            "let answer = () => 42; answer();",
+            "let adder = x => y => x + y; adder(40)(2);",
+            "let twice = (f, x) => f(f(x)); twice(x => x * 2, 5);",
+            "let abs = x => { if (x < 0) { return -x; } x }; abs(-3);",
+            "let h = () => ({\"k\": 1}); h()[\"k\"];",
+        ];
+
+        let expected: Vec<&str> = vec!["42", "42", "42", "42", "20", "3", "1"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let inputs: Vec<&str> = vec![
+            "fn add(x, y = 10) { x + y } add(1);",
+            "fn add(x, y = 10) { x + y } add(1, 2);",
+            "fn scale(x, y = x * 2) { y } scale(4);",
+            "fn sum(first, ...rest) { rest } sum(1, 2, 3);",
+            "fn sum(first, ...rest) { rest } sum(1);",
+            "fn sub(x, y) { x - y } sub(y: 2, x: 10);",
+            "fn sub(x, y) { x - y } sub(10, y: 2);",
+            "fn f(x, y = 1, z = 2) { [x, y, z] } f(0, z: 5);",
+            "let pair = (a, ...b) => [a, b]; pair(1, 2);",
+            "let add = ([a, b] = [1, 2]) => a + b; add() + add([10, 20]);",
+            "fn add(x, y) { x + y } add(1, 2, 3);",
+            "fn add(x, y = 10) { x + y } add();",
+            "fn sum(first, ...rest) { rest } sum();",
+            "fn sub(x, y) { x - y } sub(y: 2);",
+            "fn sub(x, y) { x - y } sub(1, x: 2);",
+            "fn sub(x, y) { x - y } sub(1, z: 2);",
+            "fn f(x = y) { x } f();",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "11",
+            "3",
+            "8",
+            "[2, 3]",
+            "[]",
+            "8",
+            "8",
+            "[0, 1, 5]",
+            "[1, [2]]",
+            "33",
+            "Wrong number of Arguments: add expected 2, got 3 (line 1, column 24)",
+            "Wrong number of Arguments: add expected 1 to 2, got 0 (line 1, column 29)",
+            "Wrong number of Arguments: sum expected at least 1, got 0 (line 1, column 33)",
+            "Missing Argument: x of sub (line 1, column 24)",
+            "Argument given twice: x of sub (line 1, column 24)",
+            "Unknown Argument: z of sub (line 1, column 24)",
+            "Ident not found: y (line 1, column 10)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_destructuring() {
+        let inputs: Vec<&str> = vec![
+            "let [a, b] = [1, 2]; a + b;",
+            "let [first, ...rest] = [1, 2, 3]; rest;",
+            "let [x, ...rest] = [1]; rest;",
+            "let [a, b] = [1, 2]; let [b, a] = [a, b]; [a, b];",
+            "let person = {\"name\": \"kim\", \"age\": 20}; let {name, age: years} = person; [name, years];",
+            "let [a, {k: [b, c]}] = [1, {\"k\": [2, 3]}]; a + b + c;",
+            "const [a, b] = [1, 2]; b;",
+            "fn f([a, b], {k}) { a + b + k } f([1, 2], {\"k\": 3});",
+            "let g = ([a, b]) => a * b; g([6, 7]);",
+            "fn h([a, b] = [4, 5]) { a + b } h();",
+            "let name = ({name}) => name; name({\"name\": \"kim\"});",
+            "let add = ({a, b}) => a + b; add({\"a\": 1, \"b\": 2});",
+            "let add = (a, {b, c}) => a + b + c; add(1, {\"b\": 2, \"c\": 3});",
+            "let age = ({name, age: years}) => years; age({\"name\": \"kim\", \"age\": 20});",
+            "let [a, b] = [1];",
+            "let [a] = [1, 2];",
+            "let [a, b, ...c] = [1];",
+            "let [a] = 1;",
+            "let {name} = [1];",
+            "let {name, age} = {\"name\": 1};",
+            "fn f([a, b]) { a } f(1);",
+            "const [a, b] = [1, 2]; let [b, c] = [3, 4];",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "3",
+            "[2, 3]",
+            "[]",
+            "[2, 1]",
+            "[\"kim\", 20]",
+            "6",
+            "2",
+            "6",
+            "42",
+            "9",
+            "\"kim\"",
+            "3",
+            "6",
+            "20",
+            "Cannot Destructure: Array of length 1 into [a, b] (line 1, column 1)",
+            "Cannot Destructure: Array of length 2 into [a] (line 1, column 1)",
+            "Cannot Destructure: Array of length 1 into [a, b, ...c] (line 1, column 1)",
+            "Cannot Destructure: Integer into [a] (line 1, column 1)",
+            "Cannot Destructure: Array into {name} (line 1, column 1)",
+            "Cannot Destructure: Hash without \"age\" into {name, age} (line 1, column 1)",
+            "Cannot Destructure: Integer into [a, b] (line 1, column 20)",
+            "Cannot Redeclare const: b (line 1, column 24)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_match() {
+        let inputs: Vec<&str> = vec![
+            "match (0) { 0 => \"zero\", _ => \"other\" };",
+            "match (-1) { 1 => \"one\", -1 => \"minus one\" };",
+            "match 2.0 { 2 => \"two\", _ => \"other\" };",
+            "match \"a\" { 1 => 1, true => 2, \"a\" => 3 };",
+            "match [1, 2] { [x] => x, [x, y] => x + y };",
+            "match [1, 2, 3] { [first, ...rest] => rest };",
+            "match {\"kind\": \"a\", \"size\": 3} { {kind: \"b\"} => 0, {kind: \"a\", size} => size };",
+            "match 5 { n if n < 0 => \"negative\", n if n > 0 => \"positive\", _ => \"zero\" };",
+            "let x = 1; match 2 { x => x }; x;",
+            "fn sign(n) { match n { 0 => { return 0; } m if m < 0 => { return -1; } _ => { return 1; } } } sign(-7);",
+            "let f = x => match x { [a, b] => a * b, _ => 0 }; f([6, 7]);",
+            "let x = 0; while (x < 10) { x += 1; let y = match (x) { 5 => { break; }, _ => x }; } x;",
+            "let s = 0; for (let i = 0; i < 4; i += 1) { s += match i { 2 => { continue; }, n => n }; } s;",
+            "match 3 { 1 => \"one\", 2 => \"two\" };",
+            "match [1, 2] { [x] => x };",
+            "match 1 { n if m => n };",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "\"zero\"",
+            "\"minus one\"",
+            "\"two\"",
+            "3",
+            "3",
+            "[2, 3]",
+            "3",
+            "\"positive\"",
+            "1",
+            "-1",
+            "42",
+            "5",
+            "4",
+            "Non-exhaustive Match: no arm for 3 (line 1, column 1)",
+            "Non-exhaustive Match: no arm for [1, 2] (line 1, column 1)",
+            "Ident not found: m (line 1, column 16)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_try() {
+        let inputs: Vec<&str> = vec![
+            "try { 1 } catch (e) { 2 };",
+            "try { 1 / 0 } catch (e) { e[\"message\"] };",
+            "try { 1 + true } catch ({code}) { code };",
+            "try { missing } catch ({message, code}) { [message, code] };",
+            "try { throw \"boom\"; } catch (e) { e };",
+            "try { throw {\"kind\": 1}; } catch ({kind}) { kind };",
+            "fn f() { throw 42; } try { f(); 1 } catch (e) { e + 1 };",
+            "let log = [0, 0]; try { log[0] = 1; } finally { log[1] = 2; } log;",
+            "let n = 0; try { 1 / 0 } catch { n = 1 } finally { n = n + 10 }; n;",
+            "fn g() { try { return 1; } finally { return 2; } } g();",
+            "fn h() { try { throw 1; } catch (e) { return e + 1; } } h();",
+            "let i = 0; while (true) { try { i = i + 1; if (i > 2) { break; } } finally { i = i + 10; } } i;",
+            "try { try { throw 1; } catch (e) { throw e + 1; } } catch (e) { e };",
+            "throw \"boom\";",
+            "try { 1 / 0 } finally { 1 };",
+            "try { throw 1; } catch ([a]) { a };",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "1",
+            "\"Division by Zero: 1 / 0\"",
+            "\"EVL0001\"",
+            "[\"Ident not found: missing\", \"EVL0003\"]",
+            "\"boom\"",
+            "1",
+            "43",
+            "[1, 2]",
+            "11",
+            "2",
+            "2",
+            "22",
+            "2",
+            "Uncaught: \"boom\" (line 1, column 1)",
+            "Division by Zero: 1 / 0 (line 1, column 7)",
+            "Cannot Destructure: Integer into [a] (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_result_option() {
+        let inputs: Vec<&str> = vec![
+            "Ok(1);",
+            "[Err(\"bad\"), Some([1]), None];",
+            "[is_ok(Ok(1)), is_err(Ok(1)), is_some(None), is_none(None)];",
+            "unwrap(Some(2)) + unwrap_or(Err(1), 3);",
+            "fn half(x) { if (x % 2 == 0) { Ok(x / 2) } else { Err(x) } } \
+             fn quarter(x) { Ok(half(x)? / 2 + half(x / 2)?) } \
+             [quarter(8), quarter(6), quarter(3)];",
+            "fn at(xs, i) { if (i < 2) { Some(xs[i]) } else { None } } \
+             fn sum(xs) { let a = at(xs, 0)?; Some(a + at(xs, 1)?) } [sum([3, 5]), at([3], 2)];",
+            "let f = fn(o) { for (let i = 0; i < 3; i += 1) { o?; } 1 }; [f(Some(1)), f(None)];",
+            "let g = fn(r, x = r?) { Ok(x + 1) }; [g(Ok(1)), g(Err(0))];",
+            "let Some = fn(x) { x }; Some(1);",
+            "Err(1)?; 2;",
+            "unwrap(Err(\"bad\"));",
+            "unwrap(None);",
+            "is_ok(Some(1));",
+            "1?;",
+            "Ok(1, 2);",
+            "unwrap_or(None, default: 1);",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "Ok(1)",
+            "[Err(\"bad\"), Some([1]), None]",
+            "[true, false, false, true]",
+            "5",
+            "[Ok(4), Err(3), Err(3)]",
+            "[Some(8), None]",
+            "[1, None]",
+            "[Ok(2), Err(0)]",
+            "1",
+            "Err(1)",
+            "Unwrap Failed: Err(\"bad\") (line 1, column 1)",
+            "Unwrap Failed: None (line 1, column 1)",
+            "Type Mismatched: is_ok(Option) (line 1, column 1)",
+            "Type Mismatched: Integer? (line 1, column 1)",
+            "Wrong number of Arguments: Ok expected 1, got 2 (line 1, column 1)",
+            "Unknown Argument: default of unwrap_or (line 1, column 1)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_defer() {
+        let inputs: Vec<&str> = vec![
+            "let s = \"\"; fn f() { defer s += \"a\"; defer s += \"b\"; s += \"c\"; } f(); s;",
+            "let s = \"\"; fn f() { defer s += \"d\"; return s + \"r\"; } [f(), s];",
+            "let s = \"\"; fn f() { defer s += \"d\"; 1 / 0 } try { f() } catch (e) { s };",
+            "let s = \"\"; fn f(o) { defer s += \"d\"; o? } [f(None), s];",
+            "let n = 0; fn f() { for (let i = 1; i < 4; i += 1) { let d = i; defer n = n * 10 + d; } } f(); n;",
+            "let s = \"\"; fn g() { defer s += \"g\"; } fn f() { defer s += \"f\"; g(); s += \"x\"; } f(); s;",
+            "let s = \"\"; let f = x => { if (x) { defer s += \"d\"; } s += \"b\"; }; f(false); f(true); s;",
+            "fn f() { defer missing; 1 } f();",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "\"cba\"",
+            "[\"r\", \"d\"]",
+            "\"d\"",
+            "[None, \"d\"]",
+            "321",
+            "\"gxf\"",
+            "\"bbd\"",
+            "Ident not found: missing (line 1, column 16)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_error() {
+        let inputs: Vec<&str> = vec![
+            "5 + true;",
+            "-true;",
+            "true * false;",
+            "if (true) { false / 1; }",
+            "let x = 1;\nlet y = 2;\n  x + (y == 2);",
+            "let f = fn(x) {\n  x + true\n};\nf(1);",
+            "foo;",
+            "let = 1;\n1 +;",
+        ];
+
+        let expected: Vec<&str> = vec![
+            "Type Mismatched: Integer + Bool (line 1, column 1)",
+            "Unknown Operator: -Bool (line 1, column 1)",
+            "Type Mismatched: Bool * Bool (line 1, column 1)",
+            "Type Mismatched: Bool / Integer (line 1, column 13)",
+            "Type Mismatched: Integer + Bool (line 3, column 3)",
+            "Type Mismatched: Integer + Bool (line 2, column 3)",
+            "Ident not found: foo (line 1, column 1)",
+            "PAR0001: Expected identifier, but found \"=\" (line 1, column 5)\n\
+             PAR0004: Expected an expression, but found \";\" (line 2, column 4)",
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let e = Eval::new();
+            assert_eq!(e.run(input.to_string()), expected[i]);
+        }
+    }
+}
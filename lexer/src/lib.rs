@@ -1,37 +1,79 @@
-use token::Token;
+use token::{Span, Token};
 
 pub struct Lexer {
     pub input:    Vec<char>,
     pub position: usize,
     pub ch:       char,
+
+    // where self.ch is in the source
+    pub line:   usize,
+    pub column: usize,
+    pub offset: usize,
+
+    // comments are returned as COMMENT tokens instead of being skipped
+    pub keep_comments: bool,
 }
 
 impl Lexer {
     pub fn new(input: String) -> Self {
         Self {
-            input:    input.chars().collect(),
-            position: 0,
-            ch:       ' ',
+            input:         input.chars().collect(),
+            position:      0,
+            ch:            ' ',
+            line:          1,
+            column:        1,
+            offset:        0,
+            keep_comments: false,
+        }
+    }
+
+    // for tools which need comments as trivia, like formatters
+    pub fn with_comments(input: String) -> Self {
+        Self {
+            keep_comments: true,
+            ..Self::new(input)
         }
     }
 
     pub fn next_token(&mut self) -> Token {
         self.skip_whitespaces();
+        while self.is_comment_start() {
+            let (line, column, start) = (self.line, self.column, self.offset);
+            let mut token = self.read_comment();
+            if self.keep_comments || token.token_type == token::ILLEGAL {
+                token.span = Span::new(line, column, start, self.offset);
+                return token;
+            }
+            self.skip_whitespaces();
+        }
 
-        let token = match self.ch {
-            '=' => {
-                if self.peek_char() == '=' {
+        let (line, column, start) = (self.line, self.column, self.offset);
+
+        let mut token = match self.ch {
+            '=' => match self.peek_char() {
+                '=' => {
                     self.read_char();
                     Token::new(token::EQ, "==".to_string())
                 }
+                '>' => {
+                    self.read_char();
+                    Token::new(token::ARROW, "=>".to_string())
+                }
+                _ => Token::new(token::ASSIGN, self.ch.to_string()),
+            },
+            '+' => self.with_assign(token::PLUS, token::PLUS_ASSIGN),
+            '-' => self.with_assign(token::MINUS, token::MINUS_ASSIGN),
+            '*' => {
+                if self.peek_char() == '*' {
+                    self.read_char();
+                    Token::new(token::POWER, "**".to_string())
+                }
                 else {
-                    Token::new(token::ASSIGN, self.ch.to_string())
+                    self.with_assign(token::ASTERISK, token::ASTERISK_ASSIGN)
                 }
             }
-            '+' => Token::new(token::PLUS, self.ch.to_string()),
-            '-' => Token::new(token::MINUS, self.ch.to_string()),
-            '*' => Token::new(token::ASTERISK, self.ch.to_string()),
-            '/' => Token::new(token::SLASH, self.ch.to_string()),
+            '/' => self.with_assign(token::SLASH, token::SLASH_ASSIGN),
+            '%' => self.with_assign(token::PERCENT, token::PERCENT_ASSIGN),
             '!' => {
                 if self.peek_char() == '=' {
                     self.read_char();
@@ -41,32 +83,90 @@ impl Lexer {
                     Token::new(token::BANG, self.ch.to_string())
                 }
             }
-            '<' => Token::new(token::LT, self.ch.to_string()),
-            '>' => Token::new(token::GT, self.ch.to_string()),
+            '<' => match self.peek_char() {
+                '=' => {
+                    self.read_char();
+                    Token::new(token::LTE, "<=".to_string())
+                }
+                '<' => {
+                    self.read_char();
+                    Token::new(token::SHL, "<<".to_string())
+                }
+                _ => Token::new(token::LT, self.ch.to_string()),
+            },
+            '>' => match self.peek_char() {
+                '=' => {
+                    self.read_char();
+                    Token::new(token::GTE, ">=".to_string())
+                }
+                '>' => {
+                    self.read_char();
+                    Token::new(token::SHR, ">>".to_string())
+                }
+                _ => Token::new(token::GT, self.ch.to_string()),
+            },
+            '&' => {
+                if self.peek_char() == '&' {
+                    self.read_char();
+                    Token::new(token::AND, "&&".to_string())
+                }
+                else {
+                    Token::new(token::BIT_AND, self.ch.to_string())
+                }
+            }
+            '|' => {
+                if self.peek_char() == '|' {
+                    self.read_char();
+                    Token::new(token::OR, "||".to_string())
+                }
+                else {
+                    Token::new(token::BIT_OR, self.ch.to_string())
+                }
+            }
+            '^' => Token::new(token::BIT_XOR, self.ch.to_string()),
+            '~' => Token::new(token::BIT_NOT, self.ch.to_string()),
+            '.' if self.peek_nth_char(0) == '.' && self.peek_nth_char(1) == '.' => {
+                self.read_char();
+                self.read_char();
+                Token::new(token::ELLIPSIS, "...".to_string())
+            }
             ',' => Token::new(token::COMMA, self.ch.to_string()),
             ';' => Token::new(token::SEMICOLON, self.ch.to_string()),
+            ':' => Token::new(token::COLON, self.ch.to_string()),
+            '?' => Token::new(token::QUESTION, self.ch.to_string()),
             '(' => Token::new(token::LPAREN, self.ch.to_string()),
             ')' => Token::new(token::RPAREN, self.ch.to_string()),
             '{' => Token::new(token::LBRACE, self.ch.to_string()),
             '}' => Token::new(token::RBRACE, self.ch.to_string()),
+            '[' => Token::new(token::LBRACKET, self.ch.to_string()),
+            ']' => Token::new(token::RBRACKET, self.ch.to_string()),
+            '"' => self.read_string(),
             '\0' => Token::new(token::EOF, self.ch.to_string()),
             _ => {
-                if self.is_letter() {
+                if self.ch == 'r' && (self.peek_char() == '"' || self.peek_char() == '#') {
+                    self.read_raw_string()
+                }
+                else if self.is_letter() {
                     let s = self.read_ident();
-                    return Token::new(token::look_up_ident(&s), s);
+                    let mut token = Token::new(token::look_up_ident(&s), s);
+                    token.span = Span::new(line, column, start, self.offset);
+                    return token;
                 }
                 else if self.is_digit() {
-                    return Token::new(token::INT, self.read_number());
+                    let mut token = self.read_number();
+                    token.span = Span::new(line, column, start, self.offset);
+                    return token;
                 }
                 else {
                     // panic!("LXR0001: Unexpected Character \"{}\"", self.ch);
-                    return Token::new(token::ILLEGAL, self.ch.to_string());
+                    Token::new(token::ILLEGAL, self.ch.to_string())
                 }
             }
         };
 
         self.read_char();
-        return token;
+        token.span = Span::new(line, column, start, self.offset);
+        token
     }
 }
 
@@ -78,6 +178,18 @@ impl Lexer {
     }
 
     fn read_char(&mut self) {
+        // step over the current char, the very first one is not a part of input
+        if self.position > 0 && self.position <= self.input.len() {
+            if self.ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            }
+            else {
+                self.column += 1;
+            }
+            self.offset += self.ch.len_utf8();
+        }
+
         if self.position >= self.input.len() {
             self.ch = '\0';
         }
@@ -87,40 +199,263 @@ impl Lexer {
         self.position += 1;
     }
 
+    // + or +=, the same for the other compound assignments
+    fn with_assign(&mut self, op: token::TokenType, op_assign: token::TokenType) -> Token {
+        if self.peek_char() == '=' {
+            self.read_char();
+            Token::new(op_assign, op_assign.to_string())
+        }
+        else {
+            Token::new(op, self.ch.to_string())
+        }
+    }
+
+    fn is_comment_start(&mut self) -> bool {
+        self.ch == '/' && (self.peek_char() == '/' || self.peek_char() == '*')
+    }
+
+    // // to the end of line, or /* */ which can be nested
+    // an unclosed block comment becomes ILLEGAL
+    fn read_comment(&mut self) -> Token {
+        let mut s = String::new();
+        if self.peek_char() == '/' {
+            while self.ch != '\n' && self.ch != '\0' {
+                s.push(self.ch);
+                self.read_char();
+            }
+            return Token::new(token::COMMENT, s);
+        }
+
+        let mut depth = 0;
+        loop {
+            if self.ch == '\0' {
+                return Token::new(token::ILLEGAL, s);
+            }
+            else if self.ch == '/' && self.peek_char() == '*' {
+                depth += 1;
+                s.push(self.ch);
+                self.read_char();
+            }
+            else if self.ch == '*' && self.peek_char() == '/' {
+                depth -= 1;
+                s.push(self.ch);
+                self.read_char();
+                if depth == 0 {
+                    s.push(self.ch);
+                    self.read_char();
+                    return Token::new(token::COMMENT, s);
+                }
+            }
+            s.push(self.ch);
+            self.read_char();
+        }
+    }
+
     fn read_ident(&mut self) -> String {
         let mut s = String::new();
-        while self.is_letter() {
+        while self.is_ident_continue() {
             s.push(self.ch);
             self.read_char();
         }
         s
     }
 
-    fn read_number(&mut self) -> String {
+    // 123, 1_000_000, 0x1F, 0o17, 0b1010, 1.5, 2e10 are kept as written,
+    // the parser computes the value
+    fn read_number(&mut self) -> Token {
         let mut s = String::new();
-        while self.is_digit() {
+        let radix = match (self.ch, self.peek_char()) {
+            ('0', 'x' | 'X') => 16,
+            ('0', 'o' | 'O') => 8,
+            ('0', 'b' | 'B') => 2,
+            _ => 10,
+        };
+
+        if radix == 10 {
+            self.read_digits(&mut s);
+            let mut token_type = token::INT;
+
+            // a dot or an exponent only belongs to the number when digits follow
+            if self.ch == '.' && self.peek_char().is_ascii_digit() {
+                token_type = token::FLOAT;
+                s.push(self.ch);
+                self.read_char();
+                self.read_digits(&mut s);
+            }
+            let signed = self.peek_char() == '+' || self.peek_char() == '-';
+            if (self.ch == 'e' || self.ch == 'E')
+                && (self.peek_char().is_ascii_digit()
+                    || signed && self.peek_nth_char(1).is_ascii_digit())
+            {
+                token_type = token::FLOAT;
+                s.push(self.ch);
+                self.read_char();
+                if signed {
+                    s.push(self.ch);
+                    self.read_char();
+                }
+                self.read_digits(&mut s);
+            }
+            return Token::new(token_type, s);
+        }
+
+        s.push(self.ch);
+        self.read_char();
+        s.push(self.ch);
+        self.read_char();
+
+        // take the whole word so that 0b102 is reported as a whole
+        let mut has_digit = false;
+        let mut valid = true;
+        while self.ch.is_ascii_alphanumeric() || self.ch == '_' {
+            if self.ch != '_' {
+                has_digit = true;
+                valid &= self.ch.is_digit(radix);
+            }
             s.push(self.ch);
             self.read_char();
         }
-        s
+
+        if has_digit && valid {
+            Token::new(token::INT, s)
+        }
+        else {
+            Token::new(token::ILLEGAL, s)
+        }
+    }
+
+    fn read_digits(&mut self, s: &mut String) {
+        while self.is_digit() || self.ch == '_' {
+            s.push(self.ch);
+            self.read_char();
+        }
+    }
+
+    // "..." may span lines and supports \n \t \r \0 \" \\ and \u{...} escapes
+    // ends on the closing quote, a broken string becomes ILLEGAL with its source text
+    fn read_string(&mut self) -> Token {
+        let mut s = String::new();
+        let mut source = String::from('"');
+        let mut valid = true;
+        loop {
+            self.read_char();
+            match self.ch {
+                '"' => break,
+                '\0' => return Token::new(token::ILLEGAL, source),
+                '\\' => {
+                    source.push(self.ch);
+                    self.read_char();
+                    source.push(self.ch);
+                    let escaped = match self.ch {
+                        'n' => Some('\n'),
+                        't' => Some('\t'),
+                        'r' => Some('\r'),
+                        '0' => Some('\0'),
+                        '"' => Some('"'),
+                        '\\' => Some('\\'),
+                        'u' => self.read_unicode_escape(&mut source),
+                        '\0' => return Token::new(token::ILLEGAL, source),
+                        _ => None,
+                    };
+                    match escaped {
+                        Some(c) => s.push(c),
+                        None => valid = false,
+                    }
+                    continue;
+                }
+                _ => s.push(self.ch),
+            }
+            source.push(self.ch);
+        }
+        source.push('"');
+
+        if valid {
+            Token::new(token::STRING, s)
+        }
+        else {
+            Token::new(token::ILLEGAL, source)
+        }
+    }
+
+    // {XXXX} after \u
+    fn read_unicode_escape(&mut self, source: &mut String) -> Option<char> {
+        if self.peek_char() != '{' {
+            return None;
+        }
+        self.read_char();
+        source.push(self.ch);
+
+        let mut hex = String::new();
+        while self.peek_char().is_ascii_hexdigit() {
+            self.read_char();
+            source.push(self.ch);
+            hex.push(self.ch);
+        }
+
+        if self.peek_char() != '}' {
+            return None;
+        }
+        self.read_char();
+        source.push(self.ch);
+
+        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+    }
+
+    // r"..." or r#"..."# takes everything as it is, including newlines and backslashes
+    fn read_raw_string(&mut self) -> Token {
+        let mut source = String::from('r');
+        let mut hashes = 0;
+        while self.peek_char() == '#' {
+            self.read_char();
+            source.push(self.ch);
+            hashes += 1;
+        }
+        if self.peek_char() != '"' {
+            return Token::new(token::ILLEGAL, source);
+        }
+        self.read_char();
+        source.push(self.ch);
+
+        let mut s = String::new();
+        loop {
+            self.read_char();
+            match self.ch {
+                '\0' => return Token::new(token::ILLEGAL, source),
+                '"' if (0..hashes).all(|i| self.peek_nth_char(i) == '#') => break,
+                _ => s.push(self.ch),
+            }
+            source.push(self.ch);
+        }
+        for _ in 0..hashes {
+            self.read_char();
+        }
+
+        Token::new(token::STRING, s)
     }
 
     fn peek_char(&mut self) -> char {
-        if self.position >= self.input.len() {
+        self.peek_nth_char(0)
+    }
+
+    fn peek_nth_char(&self, n: usize) -> char {
+        if self.position + n >= self.input.len() {
             '\0'
         }
         else {
-            self.input[self.position]
+            self.input[self.position + n]
         }
     }
 
+    // identifiers follow Unicode XID, so x1 and 변수 are both single identifiers
     fn is_letter(&self) -> bool {
-        let c = self.ch;
-        'a' <= c && c <= 'z' || 'A' <= c && c <= 'Z' || c == '_'
+        self.ch == '_' || unicode_ident::is_xid_start(self.ch)
+    }
+
+    fn is_ident_continue(&self) -> bool {
+        unicode_ident::is_xid_continue(self.ch)
     }
 
     fn is_digit(&self) -> bool {
-        let c = self.ch;
-        '0' <= c && c <= '9'
+        self.ch.is_ascii_digit()
     }
 }
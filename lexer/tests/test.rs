@@ -4,7 +4,7 @@ mod lexer_tests {
 
     #[test]
     fn simple_literal() {
-        let input = "=+(){},;".to_string();
+        let input = "=+(){}[],;".to_string();
 
         let expected: Vec<(TokenType, &str)> = vec![
             (token::ASSIGN, "="),
@@ -13,6 +13,8 @@ mod lexer_tests {
             (token::RPAREN, ")"),
             (token::LBRACE, "{"),
             (token::RBRACE, "}"),
+            (token::LBRACKET, "["),
+            (token::RBRACKET, "]"),
             (token::COMMA, ","),
             (token::SEMICOLON, ";"),
             (token::EOF, "\0"),
@@ -192,4 +194,270 @@ mod lexer_tests {
             assert_eq!(tok.literal, exp.1);
         }
     }
+
+    #[test]
+    fn operators() {
+        let input = "<= >= < > % ** * && & || | ^ ~ << >> += -= *= /= %= => = == ... ?".to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::LTE, "<="),
+            (token::GTE, ">="),
+            (token::LT, "<"),
+            (token::GT, ">"),
+            (token::PERCENT, "%"),
+            (token::POWER, "**"),
+            (token::ASTERISK, "*"),
+            (token::AND, "&&"),
+            (token::BIT_AND, "&"),
+            (token::OR, "||"),
+            (token::BIT_OR, "|"),
+            (token::BIT_XOR, "^"),
+            (token::BIT_NOT, "~"),
+            (token::SHL, "<<"),
+            (token::SHR, ">>"),
+            (token::PLUS_ASSIGN, "+="),
+            (token::MINUS_ASSIGN, "-="),
+            (token::ASTERISK_ASSIGN, "*="),
+            (token::SLASH_ASSIGN, "/="),
+            (token::PERCENT_ASSIGN, "%="),
+            (token::ARROW, "=>"),
+            (token::ASSIGN, "="),
+            (token::EQ, "=="),
+            (token::ELLIPSIS, "..."),
+            (token::QUESTION, "?"),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
+
+    #[test]
+    fn span() {
+        let input = "let x = 10;\n  x == 변수;".to_string();
+
+        // (line, column, start, end)
+        let expected: Vec<(TokenType, usize, usize, usize, usize)> = vec![
+            (token::LET, 1, 1, 0, 3),
+            (token::IDENT, 1, 5, 4, 5),
+            (token::ASSIGN, 1, 7, 6, 7),
+            (token::INT, 1, 9, 8, 10),
+            (token::SEMICOLON, 1, 11, 10, 11),
+            (token::IDENT, 2, 3, 14, 15),
+            (token::EQ, 2, 5, 16, 18),
+            (token::IDENT, 2, 8, 19, 25),
+            (token::SEMICOLON, 2, 10, 25, 26),
+            (token::EOF, 2, 11, 26, 26),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.span.line, exp.1);
+            assert_eq!(tok.span.column, exp.2);
+            assert_eq!(tok.span.start, exp.3);
+            assert_eq!(tok.span.end, exp.4);
+        }
+    }
+
+    #[test]
+    fn string() {
+        let input = r###"
+            "hello world"
+            "tab\tquote\" backslash\\ newline\n"
+            "\u{48}\u{1F600}"
+            "multi
+line"
+            r"raw \n string"
+            r#"raw "quoted" string"#
+            "bad \q escape"
+            "unterminated
+        "###
+        .to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::STRING, "hello world"),
+            (token::STRING, "tab\tquote\" backslash\\ newline\n"),
+            (token::STRING, "H😀"),
+            (token::STRING, "multi\nline"),
+            (token::STRING, "raw \\n string"),
+            (token::STRING, "raw \"quoted\" string"),
+            (token::ILLEGAL, "\"bad \\q escape\""),
+            (token::ILLEGAL, "\"unterminated\n        "),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
+
+    #[test]
+    fn comment() {
+        let input = "
+            // line comment
+            let x = 1; // trailing
+            /* block /* nested */ still comment */
+            x / 2;
+            /* unclosed /* */
+        "
+        .to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::LET, "let"),
+            (token::IDENT, "x"),
+            (token::ASSIGN, "="),
+            (token::INT, "1"),
+            (token::SEMICOLON, ";"),
+            (token::IDENT, "x"),
+            (token::SLASH, "/"),
+            (token::INT, "2"),
+            (token::SEMICOLON, ";"),
+            (token::ILLEGAL, "/* unclosed /* */\n        "),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
+
+    #[test]
+    fn comment_as_trivia() {
+        let input = "// doc\nfn /* a /* b */ */ x".to_string();
+
+        let expected: Vec<(TokenType, &str, usize, usize)> = vec![
+            (token::COMMENT, "// doc", 1, 1),
+            (token::FUNC, "fn", 2, 1),
+            (token::COMMENT, "/* a /* b */ */", 2, 4),
+            (token::IDENT, "x", 2, 20),
+            (token::EOF, "\0", 2, 21),
+        ];
+
+        let mut l = lexer::Lexer::with_comments(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+            assert_eq!(tok.span.line, exp.2);
+            assert_eq!(tok.span.column, exp.3);
+        }
+    }
+
+    #[test]
+    fn ident() {
+        let input = "x1 _tmp2 camelCase snake_case_3 변수 값1 café ñ 1x x·y ⅷ 🙂".to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::IDENT, "x1"),
+            (token::IDENT, "_tmp2"),
+            (token::IDENT, "camelCase"),
+            (token::IDENT, "snake_case_3"),
+            (token::IDENT, "변수"),
+            (token::IDENT, "값1"),
+            (token::IDENT, "café"),
+            (token::IDENT, "ñ"),
+            (token::INT, "1"),
+            (token::IDENT, "x"),
+            (token::IDENT, "x·y"),
+            (token::IDENT, "ⅷ"),
+            (token::ILLEGAL, "🙂"),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
+
+    #[test]
+    fn number() {
+        let input = "0 123 1_000_000 0x1F 0XfF 0o17 0b1010 0b_1 0b102 0x 0o8 12ab".to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::INT, "0"),
+            (token::INT, "123"),
+            (token::INT, "1_000_000"),
+            (token::INT, "0x1F"),
+            (token::INT, "0XfF"),
+            (token::INT, "0o17"),
+            (token::INT, "0b1010"),
+            (token::INT, "0b_1"),
+            (token::ILLEGAL, "0b102"),
+            (token::ILLEGAL, "0x"),
+            (token::ILLEGAL, "0o8"),
+            (token::INT, "12"),
+            (token::IDENT, "ab"),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
+
+    #[test]
+    fn float() {
+        let input = "1.5 0.25 2e10 1.5e-3 3E+2 1_000.000_1 1. 1.e5 2e x.1".to_string();
+
+        let expected: Vec<(TokenType, &str)> = vec![
+            (token::FLOAT, "1.5"),
+            (token::FLOAT, "0.25"),
+            (token::FLOAT, "2e10"),
+            (token::FLOAT, "1.5e-3"),
+            (token::FLOAT, "3E+2"),
+            (token::FLOAT, "1_000.000_1"),
+            (token::INT, "1"),
+            (token::ILLEGAL, "."),
+            (token::INT, "1"),
+            (token::ILLEGAL, "."),
+            (token::IDENT, "e5"),
+            (token::INT, "2"),
+            (token::IDENT, "e"),
+            (token::IDENT, "x"),
+            (token::ILLEGAL, "."),
+            (token::INT, "1"),
+            (token::EOF, "\0"),
+        ];
+
+        let mut l = lexer::Lexer::new(input);
+
+        for exp in expected.iter() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, exp.0);
+            assert_eq!(tok.literal, exp.1);
+        }
+    }
 }
@@ -1,26 +1,36 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 
 #[derive(Clone)]
 pub struct Env {
-    inner: HashMap<String, Box<dyn super::Object>>,
-    outer: Option<Rc<RefCell<Env>>>,
+    inner:     HashMap<String, Box<dyn super::Object>>,
+    constants: HashSet<String>, // names in inner declared by const
+    outer:     Option<Rc<RefCell<Env>>>,
+}
+
+// why a name could not be bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvError {
+    Undeclared,
+    Constant,
 }
 impl Env {
     pub fn new() -> Rc<RefCell<Self>> {
         let e = Self {
-            inner: HashMap::<String, Box<dyn super::Object>>::new(),
-            outer: Option::None,
+            inner:     HashMap::<String, Box<dyn super::Object>>::new(),
+            constants: HashSet::new(),
+            outer:     Option::None,
         };
         Rc::new(RefCell::new(e))
     }
 
     pub fn wrap_env(outer: Rc<RefCell<Env>>) -> Self {
         Self {
-            inner: HashMap::<String, Box<dyn super::Object>>::new(),
-            outer: Some(outer),
+            inner:     HashMap::<String, Box<dyn super::Object>>::new(),
+            constants: HashSet::new(),
+            outer:     Some(outer),
         }
     }
 
@@ -43,6 +53,40 @@ impl Env {
     pub fn set(&mut self, key: String, value: Box<dyn super::Object>) {
         self.inner.insert(key, value);
     }
+
+    // let and const in this scope, a const can not be declared over
+    pub fn declare(
+        &mut self,
+        key: String,
+        value: Box<dyn super::Object>,
+        constant: bool,
+    ) -> Result<(), EnvError> {
+        if self.constants.contains(&key) {
+            return Err(EnvError::Constant);
+        }
+        if constant {
+            self.constants.insert(key.clone());
+        }
+        self.inner.insert(key, value);
+        Ok(())
+    }
+
+    // updates the nearest scope which already has the name
+    pub fn assign(&mut self, key: &String, value: Box<dyn super::Object>) -> Result<(), EnvError> {
+        if self.constants.contains(key) {
+            Err(EnvError::Constant)
+        }
+        else if let Some(v) = self.inner.get_mut(key) {
+            *v = value;
+            Ok(())
+        }
+        else if let Some(o) = &self.outer {
+            o.borrow_mut().assign(key, value)
+        }
+        else {
+            Err(EnvError::Undeclared)
+        }
+    }
 }
 impl Debug for Env {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,68 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use super::{Object, ObjectType};
+
+// only values which can be compared exactly are usable as keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Integer(i64),
+    Bool(bool),
+    Str(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct HashPair {
+    pub key:   Box<dyn Object>,
+    pub value: Box<dyn Object>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Hash {
+    pub pairs: Vec<HashPair>, // in insertion order
+    index:     HashMap<HashKey, usize>,
+}
+impl Hash {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &HashKey) -> Option<&dyn Object> {
+        self.index.get(key).map(|&i| &*self.pairs[i].value)
+    }
+
+    // an existing key keeps its place and only the value is replaced
+    pub fn insert(&mut self, hash_key: HashKey, key: Box<dyn Object>, value: Box<dyn Object>) {
+        match self.index.get(&hash_key) {
+            Some(&i) => self.pairs[i].value = value,
+            None => {
+                self.index.insert(hash_key, self.pairs.len());
+                self.pairs.push(HashPair { key, value });
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+impl Object for Hash {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Hash"
+    }
+    fn inspect(&self) -> String {
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|pair| format!("{}: {}", pair.key.inspect(), pair.value.inspect()))
+            .collect();
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
@@ -1,7 +1,9 @@
 mod environment;
+mod hash;
 pub use environment::*;
+pub use hash::*;
 
-use ast;
+use ast::Span;
 use std::any::Any;
 use std::cell::RefCell;
 use std::fmt::Debug;
@@ -13,6 +15,9 @@ pub trait Object: Debug + BoxClone {
     fn as_any(&self) -> &dyn Any;
     fn object_type(&self) -> ObjectType;
     fn inspect(&self) -> String;
+    fn hash_key(&self) -> Option<HashKey> {
+        None
+    }
 }
 
 //https://stackoverflow.com/questions/30353462/how-to-clone-a-struct-storing-a-boxed-trait-object
@@ -43,7 +48,7 @@ impl Object for Never {
         "Never"
     }
     fn inspect(&self) -> String {
-        format!("never_inspected")
+        "never_inspected".to_string()
     }
 }
 
@@ -57,7 +62,7 @@ impl Object for Null {
         "Null"
     }
     fn inspect(&self) -> String {
-        format!("null")
+        "null".to_string()
     }
 }
 pub const NULL: Null = Null {};
@@ -76,6 +81,44 @@ impl Object for Integer {
     fn inspect(&self) -> String {
         format!("{}", self.value)
     }
+    fn hash_key(&self) -> Option<HashKey> {
+        Some(HashKey::Integer(self.value))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Float {
+    pub value: f64,
+}
+impl Object for Float {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Float"
+    }
+    fn inspect(&self) -> String {
+        format!("{:?}", self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Str {
+    pub value: String,
+}
+impl Object for Str {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "String"
+    }
+    fn inspect(&self) -> String {
+        format!("{:?}", self.value)
+    }
+    fn hash_key(&self) -> Option<HashKey> {
+        Some(HashKey::Str(self.value.clone()))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -92,6 +135,9 @@ impl Object for Bool {
     fn inspect(&self) -> String {
         format!("{}", self.value)
     }
+    fn hash_key(&self) -> Option<HashKey> {
+        Some(HashKey::Bool(*self.value))
+    }
 }
 pub const TRUE: Bool = Bool { value: &true };
 pub const FALSE: Bool = Bool { value: &false };
@@ -104,6 +150,62 @@ pub fn static_bool_obj(b: bool) -> Bool {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Array {
+    pub elements: Vec<Box<dyn Object>>,
+}
+impl Object for Array {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Array"
+    }
+    fn inspect(&self) -> String {
+        let elements: Vec<String> = self.elements.iter().map(|e| e.inspect()).collect();
+        format!("[{}]", elements.join(", "))
+    }
+}
+
+// Ok(value) or Err(value)
+#[derive(Debug, Clone)]
+pub struct ResultValue {
+    pub ok:    bool,
+    pub value: Box<dyn Object>,
+}
+impl Object for ResultValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Result"
+    }
+    fn inspect(&self) -> String {
+        let variant = if self.ok { "Ok" } else { "Err" };
+        format!("{}({})", variant, self.value.inspect())
+    }
+}
+
+// Some(value) or None
+#[derive(Debug, Clone)]
+pub struct OptionValue {
+    pub value: Option<Box<dyn Object>>,
+}
+impl Object for OptionValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Option"
+    }
+    fn inspect(&self) -> String {
+        match &self.value {
+            Some(value) => format!("Some({})", value.inspect()),
+            None => "None".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ReturnValue {
     pub value: Box<dyn Object>,
@@ -116,13 +218,49 @@ impl Object for ReturnValue {
         "ReturnValue"
     }
     fn inspect(&self) -> String {
-        format!("{}", self.value.inspect())
+        self.value.inspect()
+    }
+}
+
+// break and continue travel up like ReturnValue until the loop they belong to
+#[derive(Debug, Clone)]
+pub struct Break {
+    pub label: Option<String>,
+}
+impl Object for Break {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Break"
+    }
+    fn inspect(&self) -> String {
+        "break".to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Continue {
+    pub label: Option<String>,
+}
+impl Object for Continue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Continue"
+    }
+    fn inspect(&self) -> String {
+        "continue".to_string()
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Error {
-    pub value: String,
+    pub value:  String,
+    pub code:   &'static str,
+    pub span:   Option<Span>, // where in the source the error is raised
+    pub thrown: Option<Box<dyn Object>>, // what a throw statement raised
 }
 impl Object for Error {
     fn as_any(&self) -> &dyn Any {
@@ -132,13 +270,17 @@ impl Object for Error {
         "Error"
     }
     fn inspect(&self) -> String {
-        format!("{}", self.value)
+        match self.span {
+            Some(span) => format!("{} ({})", self.value, span),
+            None => self.value.clone(),
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Function {
-    pub parameters: Vec<ast::Identifier>,
+    pub name:       Option<String>, // None for fn literals
+    pub parameters: Vec<ast::Parameter>,
     pub body:       Vec<ast::Statement>,
     pub env:        Rc<RefCell<Env>>,
 }
@@ -147,9 +289,45 @@ impl Object for Function {
         self
     }
     fn object_type(&self) -> ObjectType {
-        "Funciton"
+        "Function"
+    }
+    fn inspect(&self) -> String {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let body = self
+            .body
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        match &self.name {
+            Some(name) => format!("fn {}({}) {{ {} }}", name, parameters, body),
+            None => format!("fn({}) {{ {} }}", parameters, body),
+        }
+    }
+}
+
+pub type BuiltinFunction = fn(Vec<Box<dyn Object>>) -> Box<dyn Object>;
+
+// a function written in rust, like Some or unwrap
+#[derive(Debug, Clone)]
+pub struct Builtin {
+    pub name:  &'static str,
+    pub arity: usize, // func is only called with this many arguments
+    pub func:  BuiltinFunction,
+}
+impl Object for Builtin {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn object_type(&self) -> ObjectType {
+        "Builtin"
     }
     fn inspect(&self) -> String {
-        format!("fn() {{}}") // todo
+        format!("builtin {}", self.name)
     }
 }
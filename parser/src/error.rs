@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter};
+
+use token::{Span, Token, TokenType};
+
+pub const NO_IDENT_AFTER_LET: &str = "PAR0001";
+pub const NO_ASSIGN_AFTER_IDENT: &str = "PAR0002";
+pub const UNEXPECTED_TOKEN: &str = "PAR0003";
+pub const NO_PREFIX_PARSE_FN: &str = "PAR0004";
+pub const ILLEGAL_TOKEN: &str = "PAR0005";
+pub const INTEGER_OUT_OF_RANGE: &str = "PAR0006";
+pub const FLOAT_OUT_OF_RANGE: &str = "PAR0007";
+pub const OUTSIDE_OF_LOOP: &str = "PAR0008";
+pub const UNDEFINED_LABEL: &str = "PAR0009";
+pub const LABEL_WITHOUT_LOOP: &str = "PAR0010";
+pub const INVALID_ASSIGN_TARGET: &str = "PAR0011";
+pub const ASSIGN_TO_CONST: &str = "PAR0012";
+pub const INVALID_PARAMETER: &str = "PAR0013";
+pub const INVALID_ARGUMENT: &str = "PAR0014";
+pub const INVALID_PATTERN: &str = "PAR0015";
+pub const OUTSIDE_OF_FUNCTION: &str = "PAR0016";
+
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub code:     &'static str,
+    pub message:  String,
+    pub expected: Option<TokenType>,
+    pub found:    TokenType,
+    pub span:     Span,
+}
+
+impl ParseError {
+    pub fn new(code: &'static str, message: String, found: &Token) -> Self {
+        Self {
+            code,
+            message,
+            expected: None,
+            found: found.token_type,
+            span: found.span,
+        }
+    }
+
+    pub fn unexpected(code: &'static str, expected: TokenType, found: &Token) -> Self {
+        Self {
+            code,
+            message: format!(
+                "Expected {}, but found {}",
+                describe(expected),
+                describe(&found.literal)
+            ),
+            expected: Some(expected),
+            found: found.token_type,
+            span: found.span,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.code, self.message, self.span)
+    }
+}
+
+// how a token type or literal is shown in messages, quotes inside it are escaped
+pub(super) fn describe(s: &str) -> String {
+    match s {
+        "\0" | token::EOF => "end of input".to_string(),
+        token::IDENT => "identifier".to_string(),
+        _ => format!("{:?}", s),
+    }
+}
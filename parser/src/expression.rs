@@ -1,9 +1,14 @@
+use super::error::{self, ParseError};
+use super::pattern::duplicate_name;
 use super::Parser;
 use super::Priority;
-use token::TokenType;
+use std::collections::HashMap;
+use token::{Span, TokenType};
 
-pub(super) type PrefixParseFn = fn(&mut Parser) -> ast::Expression;
-pub(super) type InfixParseFn = fn(&mut Parser, ast::Expression) -> ast::Expression;
+pub(super) type PrefixParseFn = fn(&mut Parser) -> Result<ast::Expression, ParseError>;
+pub(super) type InfixParseFn =
+    fn(&mut Parser, ast::Expression) -> Result<ast::Expression, ParseError>;
+type NamedArguments = Vec<(ast::Identifier, ast::Expression)>;
 
 // for
 impl Parser {
@@ -19,10 +24,13 @@ impl Parser {
         self.infix_parse_funcs.insert(token_type, infix_parse_fn);
     }
 
-    pub(super) fn parse_expression(&mut self, priority: Priority) -> ast::Expression {
+    pub(super) fn parse_expression(
+        &mut self,
+        priority: Priority,
+    ) -> Result<ast::Expression, ParseError> {
         let prefix = self.prefix_parse_funcs.get(self.curr_token.token_type);
         if let Some(prefix) = prefix {
-            let mut left_exp = prefix(self);
+            let mut left_exp = prefix(self)?;
 
             while self.next_token.token_type != token::SEMICOLON
                 && priority < self.next_precedence()
@@ -33,30 +41,66 @@ impl Parser {
                     .cloned();
                 if let Some(infix) = infix {
                     self.next_token();
-                    left_exp = infix(self, left_exp);
+                    left_exp = infix(self, left_exp)?;
                 }
                 else {
                     break;
                 }
             }
 
-            left_exp
+            Ok(left_exp)
+        }
+        else if self.curr_token.token_type == token::ILLEGAL {
+            Err(ParseError::new(
+                error::ILLEGAL_TOKEN,
+                format!(
+                    "Illegal token {}",
+                    error::describe(&self.curr_token.literal)
+                ),
+                &self.curr_token,
+            ))
         }
         else {
-            ast::Expression::Undefined
+            Err(ParseError::new(
+                error::NO_PREFIX_PARSE_FN,
+                format!(
+                    "Expected an expression, but found {}",
+                    error::describe(&self.curr_token.literal)
+                ),
+                &self.curr_token,
+            ))
         }
     }
 
-    pub(super) fn parse_block_statement(&mut self) -> Vec<ast::Statement> {
+    // a broken statement is reported and skipped, the rest of the block is still parsed
+    pub(super) fn parse_block_statement(&mut self) -> Result<Vec<ast::Statement>, ParseError> {
+        self.scopes.push(HashMap::new());
         let mut block_stmt = vec![];
         while self.curr_token.token_type != token::RBRACE
             && self.curr_token.token_type != token::EOF
         {
-            let stmt = self.parse_statement();
-            block_stmt.push(stmt);
+            match self.parse_statement() {
+                Ok(stmt) => block_stmt.push(stmt),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.synchronize();
+                    if self.curr_token.token_type == token::RBRACE {
+                        break;
+                    }
+                }
+            }
             self.next_token();
         }
-        block_stmt
+        self.scopes.pop();
+
+        if self.curr_token.token_type == token::EOF {
+            return Err(ParseError::unexpected(
+                error::UNEXPECTED_TOKEN,
+                token::RBRACE,
+                &self.curr_token,
+            ));
+        }
+        Ok(block_stmt)
     }
 }
 
@@ -64,7 +108,7 @@ impl Parser {
     pub(super) fn curr_precedence(&self) -> Priority {
         let p = self.precedences.get(self.curr_token.token_type);
         if let Some(p) = p {
-            p.clone()
+            *p
         }
         else {
             Priority::Lowest
@@ -72,9 +116,12 @@ impl Parser {
     }
 
     pub(super) fn next_precedence(&self) -> Priority {
+        if self.arm_head && self.next_token.token_type == token::ARROW {
+            return Priority::Lowest;
+        }
         let p = self.precedences.get(self.next_token.token_type);
         if let Some(p) = p {
-            p.clone()
+            *p
         }
         else {
             Priority::Lowest
@@ -83,203 +130,957 @@ impl Parser {
 }
 
 impl Parser {
-    pub(super) fn parse_ident(&mut self) -> ast::Expression {
-        ast::Expression::Ident(ast::Identifier {
+    pub(super) fn parse_ident(&mut self) -> Result<ast::Expression, ParseError> {
+        Ok(ast::Expression::Ident(ast::Identifier {
             token: self.curr_token.token_type,
             value: self.curr_token.literal.clone(),
+            span:  self.curr_token.span,
+        }))
+    }
+
+    pub(super) fn parse_integer_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        let value = integer_magnitude(&self.curr_token.literal)
+            .and_then(|v| i64::try_from(v).ok())
+            .ok_or_else(|| self.integer_out_of_range())?;
+
+        Ok(ast::Expression::IntegerLiteral {
+            token: self.curr_token.token_type,
+            value,
+            span: self.curr_token.span,
         })
     }
 
-    pub(super) fn parse_integer_literal(&mut self) -> ast::Expression {
-        ast::Expression::IntegerLiteral {
+    fn integer_out_of_range(&self) -> ParseError {
+        ParseError::new(
+            error::INTEGER_OUT_OF_RANGE,
+            format!(
+                "Integer literal {} is out of range for i64",
+                self.curr_token.literal
+            ),
+            &self.curr_token,
+        )
+    }
+
+    pub(super) fn parse_float_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        let value = self
+            .curr_token
+            .literal
+            .replace('_', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| {
+                ParseError::new(
+                    error::FLOAT_OUT_OF_RANGE,
+                    format!(
+                        "Float literal {} is out of range for f64",
+                        self.curr_token.literal
+                    ),
+                    &self.curr_token,
+                )
+            })?;
+
+        Ok(ast::Expression::FloatLiteral {
             token: self.curr_token.token_type,
-            value: self.curr_token.literal.parse().unwrap(),
-        }
+            value,
+            span: self.curr_token.span,
+        })
+    }
+
+    pub(super) fn parse_string_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        Ok(ast::Expression::StringLiteral {
+            token: self.curr_token.token_type,
+            value: self.curr_token.literal.clone(),
+            span:  self.curr_token.span,
+        })
     }
 
-    pub(super) fn parse_prefix_expression(&mut self) -> ast::Expression {
+    pub(super) fn parse_prefix_expression(&mut self) -> Result<ast::Expression, ParseError> {
         let token = self.curr_token.token_type;
         let operator = self.curr_token.literal.clone();
+        let start = self.curr_token.span;
         self.next_token();
-        let right = Box::new(self.parse_expression(Priority::Prefix));
 
-        ast::Expression::Prefix {
+        // i64::MIN is only writable as a negative literal, its magnitude does not fit in i64
+        if token == token::MINUS
+            && self.curr_token.token_type == token::INT
+            && integer_magnitude(&self.curr_token.literal) == Some(i64::MIN.unsigned_abs())
+        {
+            return Ok(ast::Expression::IntegerLiteral {
+                token: self.curr_token.token_type,
+                value: i64::MIN,
+                span:  start.to(self.curr_token.span),
+            });
+        }
+
+        let right = Box::new(self.parse_expression(Priority::Prefix)?);
+
+        Ok(ast::Expression::Prefix {
             token,
             operator,
             right,
-        }
+            span: start.to(self.curr_token.span),
+        })
     }
 
-    pub(super) fn parse_infix_expression(&mut self, left: ast::Expression) -> ast::Expression {
+    pub(super) fn parse_infix_expression(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
         let token = self.curr_token.token_type;
         let operator = self.curr_token.literal.clone();
-        let precedence = self.curr_precedence();
+        // a right associative operator lets the same operator continue on its right side
+        let precedence = match token {
+            token::POWER => Priority::Prefix,
+            _ => self.curr_precedence(),
+        };
         self.next_token();
+        let right = Box::new(self.parse_expression(precedence)?);
 
-        ast::Expression::Infix {
+        Ok(ast::Expression::Infix {
             token,
+            span: left.span().to(self.curr_token.span),
             left: Box::new(left),
             operator,
-            right: Box::new(self.parse_expression(precedence)),
+            right,
+        })
+    }
+
+    // a = b = c is a = (b = c)
+    pub(super) fn parse_assign_expression(
+        &mut self,
+        target: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        if !target.is_place() {
+            return Err(ParseError::new(
+                error::INVALID_ASSIGN_TARGET,
+                format!("Cannot assign to {}", target),
+                &self.curr_token,
+            ));
         }
+
+        let mut root = &target;
+        while let ast::Expression::Index { left, .. } = root {
+            root = left;
+        }
+        if let ast::Expression::Ident(ident) = root {
+            if self.is_const(&ident.value) {
+                return Err(ParseError::new(
+                    error::ASSIGN_TO_CONST,
+                    format!("Cannot assign to const '{}'", ident.value),
+                    &self.curr_token,
+                ));
+            }
+        }
+
+        let token = self.curr_token.token_type;
+        let operator = self.curr_token.literal.clone();
+        self.next_token();
+        let value = Box::new(self.parse_expression(Priority::Lowest)?);
+
+        Ok(ast::Expression::Assign {
+            token,
+            span: target.span().to(self.curr_token.span),
+            target: Box::new(target),
+            operator,
+            value,
+        })
     }
 
-    pub(super) fn parse_boolean(&mut self) -> ast::Expression {
+    pub(super) fn parse_boolean(&mut self) -> Result<ast::Expression, ParseError> {
         let token = self.curr_token.token_type;
 
-        ast::Expression::Bool {
+        Ok(ast::Expression::Bool {
             token,
             value: self.curr_token.token_type == token::TRUE,
-        }
+            span: self.curr_token.span,
+        })
     }
 
-    pub(super) fn parse_grouped_expression(&mut self) -> ast::Expression {
-        self.next_token();
-        let expression = self.parse_expression(Priority::Lowest);
-        if !self.expect_next(token::RPAREN) {
-            ast::Expression::Undefined
+    // (a + b) is a group, and (), (a) or (a, [b, c] = d, ...e) followed by => are parameters
+    // of an arrow function, parsed like the ones of fn
+    pub(super) fn parse_grouped_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        // (
+        let start = self.curr_token.span;
+        if self.opens_arrow_parameters() {
+            self.next_token();
+            let parameters = self.parse_function_parameters()?;
+            self.expect_next(token::ARROW)?;
+            return self.parse_arrow_body(start, parameters);
         }
-        else {
-            expression
+
+        self.next_token();
+        let expression = self.out_of_arm_head(|p| p.parse_expression(Priority::Lowest))?;
+        self.expect_next(token::RPAREN)?;
+        Ok(expression)
+    }
+
+    // whether the closing ) of the current ( is followed by =>,
+    // which ends the head of a match arm instead
+    fn opens_arrow_parameters(&mut self) -> bool {
+        if self.arm_head {
+            return false;
         }
+        let first = self.next_token.clone();
+        self.scan_ahead(|lexer| {
+            let mut depth = 1;
+            let mut token = first;
+            loop {
+                match token.token_type {
+                    token::LPAREN => depth += 1,
+                    token::RPAREN => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return lexer.next_token().token_type == token::ARROW;
+                        }
+                    }
+                    token::EOF => return false,
+                    _ => {}
+                }
+                token = lexer.next_token();
+            }
+        })
     }
 
-    pub(super) fn parse_if_expression(&mut self) -> ast::Expression {
-        // if
-        let token = self.curr_token.token_type;
+    // x => body
+    pub(super) fn parse_arrow_function(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        let start = left.span();
+        let parameter = self.arrow_parameter(left)?;
+        self.parse_arrow_body(start, vec![parameter])
+    }
+
+    // x, x = default or a pattern written like an array or hash literal
+    fn arrow_parameter(&self, expression: ast::Expression) -> Result<ast::Parameter, ParseError> {
+        let (target, default) = match expression {
+            ast::Expression::Assign {
+                target,
+                operator,
+                value,
+                ..
+            } if operator == "=" => (*target, Some(*value)),
+            _ => (expression, None),
+        };
 
-        // (condition)
-        if !self.expect_next(token::LPAREN) {
-            return ast::Expression::Undefined;
+        match expression_pattern(&target) {
+            Some(pattern) => Ok(ast::Parameter {
+                pattern,
+                default,
+                rest: false,
+            }),
+            None => {
+                let mut err = ParseError::new(
+                    error::INVALID_PARAMETER,
+                    format!("Invalid parameter {}", target),
+                    &self.curr_token,
+                );
+                err.span = target.span();
+                Err(err)
+            }
         }
+    }
+
+    // an expression or a { block } after =>, the current token is =>
+    fn parse_arrow_body(
+        &mut self,
+        start: Span,
+        parameters: Vec<ast::Parameter>,
+    ) -> Result<ast::Expression, ParseError> {
+        let token = self.curr_token.token_type;
         self.next_token();
 
-        let condition = Box::new(self.parse_expression(Priority::Lowest));
+        let body = self.in_function(&parameters, Parser::parse_short_body)?;
 
-        if !self.expect_next(token::RPAREN) {
-            return ast::Expression::Undefined;
+        Ok(ast::Expression::FunctionLiteral {
+            token,
+            parameters,
+            body,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // a { block } or a single expression, as the body of an arrow function or a match arm
+    fn parse_short_body(&mut self) -> Result<Vec<ast::Statement>, ParseError> {
+        if self.curr_token.token_type == token::LBRACE {
+            self.next_token();
+            return self.parse_block_statement();
         }
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        let expression = self.parse_expression(Priority::Lowest)?;
+        Ok(vec![ast::Statement::Expr {
+            token,
+            expression,
+            span: start.to(self.curr_token.span),
+        }])
+    }
+
+    pub(super) fn parse_if_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        // if
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        // condition, the parentheses around it are optional
+        self.next_token();
+        let condition = Box::new(self.parse_expression(Priority::Lowest)?);
 
         // { consequence }
-        if !self.expect_next(token::LBRACE) {
-            return ast::Expression::Undefined;
-        }
+        self.expect_next(token::LBRACE)?;
         self.next_token();
+        let consequence = self.parse_block_statement()?;
 
-        // else { alternative }
-        let consequence = self.parse_block_statement();
+        // else { alternative } or else if ..
         let mut alternative = vec![];
-        if self.expect_next(token::ELSE) {
-            if !self.expect_next(token::LBRACE) {
-                return ast::Expression::Undefined;
+        if self.next_if(token::ELSE) {
+            if self.next_if(token::IF) {
+                let elif_start = self.curr_token.span;
+                let elif = self.parse_if_expression()?;
+                alternative.push(ast::Statement::Expr {
+                    token:      token::IF,
+                    expression: elif,
+                    span:       elif_start.to(self.curr_token.span),
+                });
+            }
+            else {
+                self.expect_next(token::LBRACE)?;
+                self.next_token();
+                alternative = self.parse_block_statement()?;
             }
-            self.next_token();
-            alternative = self.parse_block_statement();
         }
 
-        ast::Expression::If {
+        Ok(ast::Expression::If {
             token,
             condition,
             consequence,
             alternative,
-        }
+            span: start.to(self.curr_token.span),
+        })
     }
 
-    pub(super) fn parse_function_literal(&mut self) -> ast::Expression {
-        // fn
+    // try { } catch (e) { } finally { }, one of catch and finally can be left out
+    pub(super) fn parse_try_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        // try { body }
         let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        self.expect_next(token::LBRACE)?;
+        self.next_token();
+        let body = self.parse_block_statement()?;
 
-        // ( params )
-        if !self.expect_next(token::LPAREN) {
-            return ast::Expression::Undefined;
+        // catch (param) { handler }
+        let mut param = None;
+        let mut handler = None;
+        if self.next_if(token::CATCH) {
+            if self.next_if(token::LPAREN) {
+                self.next_token();
+                let pattern = self.parse_pattern()?;
+                self.check_pattern(&pattern)?;
+                self.expect_next(token::RPAREN)?;
+                param = Some(Box::new(pattern));
+            }
+            self.expect_next(token::LBRACE)?;
+            self.next_token();
+
+            // the caught error is only seen in the handler
+            self.scopes.push(
+                param
+                    .iter()
+                    .flat_map(|param| param.names())
+                    .map(|name| (name.value.clone(), false))
+                    .collect(),
+            );
+            let block = self.parse_block_statement();
+            self.scopes.pop();
+            handler = Some(block?);
         }
-        self.next_token();
 
-        let parameters = self.parse_function_parameters();
+        // finally { finalizer }
+        let mut finalizer = None;
+        if self.next_if(token::FINALLY) {
+            self.expect_next(token::LBRACE)?;
+            self.next_token();
+            finalizer = Some(self.parse_block_statement()?);
+        }
 
-        // { body }
-        if !self.expect_next(token::LBRACE) {
-            return ast::Expression::Undefined;
+        if handler.is_none() && finalizer.is_none() {
+            // step onto the token, recovering from the closing brace would leave it as a statement
+            self.next_token();
+            return Err(ParseError::new(
+                error::UNEXPECTED_TOKEN,
+                format!(
+                    "Expected \"catch\" or \"finally\", but found {}",
+                    error::describe(&self.curr_token.literal)
+                ),
+                &self.curr_token,
+            ));
         }
+
+        Ok(ast::Expression::Try {
+            token,
+            body,
+            param,
+            handler,
+            finalizer,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // match (value) { pattern => body, pattern if guard => { block } }.
+    // commas between arms are optional after a block
+    pub(super) fn parse_match_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        // match
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        // subject, the parentheses around it are optional
         self.next_token();
-        let body = self.parse_block_statement();
+        let subject = Box::new(self.parse_expression(Priority::Lowest)?);
 
-        ast::Expression::FunctionLiteral {
+        // { arms }, like a block it recovers from an error at its closing brace
+        self.expect_next(token::LBRACE)?;
+        let mut arms = vec![];
+        while !self.next_if(token::RBRACE) {
+            self.next_token();
+            // an arm is followed by a comma or the closing brace
+            let arm = self.parse_match_arm().and_then(|arm| {
+                if self.next_if(token::COMMA) || self.curr_token.token_type == token::RBRACE {
+                    return Ok((arm, false));
+                }
+                self.expect_next(token::RBRACE)?;
+                Ok((arm, true))
+            });
+            match arm {
+                Ok((arm, closed)) => {
+                    arms.push(arm);
+                    if closed {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    self.errors.push(e);
+                    self.skip_to_closing_brace();
+                    break;
+                }
+            }
+        }
+
+        Ok(ast::Expression::Match {
             token,
-            parameters,
+            subject,
+            arms,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // moves to the brace which closes the one the current token is in
+    fn skip_to_closing_brace(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.curr_token.token_type {
+                token::EOF => return,
+                token::RBRACE if depth == 0 => return,
+                token::LBRACE => depth += 1,
+                token::RBRACE => depth -= 1,
+                _ => {}
+            }
+            self.next_token();
+        }
+    }
+
+    fn parse_match_arm(&mut self) -> Result<ast::MatchArm, ParseError> {
+        // pattern if guard
+        let outer = std::mem::replace(&mut self.arm_head, true);
+        let head = self.parse_match_arm_head();
+        self.arm_head = false;
+
+        // => body, where the names bound by the pattern are declared
+        let body = head.and_then(|head| {
+            self.expect_next(token::ARROW)?;
+            self.next_token();
+            self.scopes.push(
+                head.0
+                    .names()
+                    .iter()
+                    .map(|name| (name.value.clone(), false))
+                    .collect(),
+            );
+            let body = self.parse_short_body();
+            self.scopes.pop();
+            Ok((head, body?))
+        });
+        self.arm_head = outer;
+
+        let ((pattern, guard), body) = body?;
+        Ok(ast::MatchArm {
+            pattern,
+            guard,
             body,
+        })
+    }
+
+    // parses something nested in the head of a match arm, like a group, call arguments or
+    // a function literal, where => is an arrow again
+    fn out_of_arm_head<T>(
+        &mut self,
+        parse: fn(&mut Parser) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let arm_head = std::mem::replace(&mut self.arm_head, false);
+        let rlt = parse(self);
+        self.arm_head = arm_head;
+        rlt
+    }
+
+    fn parse_match_arm_head(
+        &mut self,
+    ) -> Result<(ast::Pattern, Option<ast::Expression>), ParseError> {
+        let pattern = self.parse_pattern()?;
+        self.check_pattern(&pattern)?;
+
+        let mut guard = None;
+        if self.next_if(token::IF) {
+            self.next_token();
+            guard = Some(self.parse_expression(Priority::Lowest)?);
         }
+        Ok((pattern, guard))
+    }
+
+    pub(super) fn parse_function_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        // fn
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        let (parameters, body) = self.out_of_arm_head(Parser::parse_function_rest)?;
+
+        Ok(ast::Expression::FunctionLiteral {
+            token,
+            parameters,
+            body,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // (params) { body } after fn or the name of a declared function
+    pub(super) fn parse_function_rest(
+        &mut self,
+    ) -> Result<(Vec<ast::Parameter>, Vec<ast::Statement>), ParseError> {
+        // ( params )
+        self.expect_next(token::LPAREN)?;
+        self.next_token();
+
+        let parameters = self.parse_function_parameters()?;
+
+        // { body }
+        self.expect_next(token::LBRACE)?;
+        self.next_token();
+
+        let body = self.in_function(&parameters, Parser::parse_block_statement)?;
+        Ok((parameters, body))
+    }
+
+    // parses a function body where the parameters are declared,
+    // loops outside of the function can not be broken from inside
+    fn in_function<T>(
+        &mut self,
+        parameters: &[ast::Parameter],
+        parse: fn(&mut Parser) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let loops = std::mem::take(&mut self.loops);
+        self.functions += 1;
+        self.scopes.push(
+            parameters
+                .iter()
+                .flat_map(|param| param.pattern.names())
+                .map(|name| (name.value.clone(), false))
+                .collect(),
+        );
+        let body = parse(self);
+        self.scopes.pop();
+        self.functions -= 1;
+        self.loops = loops;
+        body
     }
 
     pub(super) fn parse_function_call_expression(
         &mut self,
         func: ast::Expression,
-    ) -> ast::Expression {
+    ) -> Result<ast::Expression, ParseError> {
         // (
         let token = self.curr_token.token_type;
         self.next_token();
 
-        let args = self.parse_function_arguments();
+        let (args, named) = self.out_of_arm_head(Parser::parse_call_arguments)?;
 
-        ast::Expression::FunctionCall {
+        Ok(ast::Expression::FunctionCall {
             token,
+            span: func.span().to(self.curr_token.span),
             func: Box::new(func),
             args,
-        }
+            named,
+        })
     }
 
-    //////////////////
-
-    pub(super) fn parse_function_parameters(&mut self) -> Vec<ast::Identifier> {
-        let mut params = vec![];
+    // positional arguments first, then named ones like f(1, y: 2)
+    fn parse_call_arguments(
+        &mut self,
+    ) -> Result<(Vec<ast::Expression>, NamedArguments), ParseError> {
+        let mut args = vec![];
+        let mut named: NamedArguments = vec![];
 
         if self.curr_token.token_type == token::RPAREN {
-            return params;
+            return Ok((args, named));
         }
 
-        params.push(ast::Identifier {
-            token: token::IDENT,
-            value: self.curr_token.literal.clone(),
-        });
+        loop {
+            if self.curr_token.token_type == token::IDENT
+                && self.next_token.token_type == token::COLON
+            {
+                let name = ast::Identifier {
+                    token: token::IDENT,
+                    value: self.curr_token.literal.clone(),
+                    span:  self.curr_token.span,
+                };
+                if named.iter().any(|(other, _)| other.value == name.value) {
+                    return Err(ParseError::new(
+                        error::INVALID_ARGUMENT,
+                        format!("Argument '{}' is given more than once", name.value),
+                        &self.curr_token,
+                    ));
+                }
+                self.next_token();
+                self.next_token();
+                named.push((name, self.parse_expression(Priority::Lowest)?));
+            }
+            else if named.is_empty() {
+                args.push(self.parse_expression(Priority::Lowest)?);
+            }
+            else {
+                return Err(ParseError::new(
+                    error::INVALID_ARGUMENT,
+                    "Positional argument after named arguments".to_string(),
+                    &self.curr_token,
+                ));
+            }
 
-        while self.next_token.token_type == token::COMMA {
+            if !self.next_if(token::COMMA) {
+                break;
+            }
             self.next_token();
+        }
+
+        self.expect_next(token::RPAREN)?;
+
+        Ok((args, named))
+    }
+
+    pub(super) fn parse_array_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        // [
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        self.next_token();
+
+        let elements = self.parse_expression_list(token::RBRACKET)?;
+
+        Ok(ast::Expression::ArrayLiteral {
+            token,
+            elements,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // a brace in prefix position opens a hash literal when its first expression is followed
+    // by a colon and a block otherwise, {} is an empty hash.
+    // bodies of if, fn and loops are parsed by parse_block_statement right after their header
+    pub(super) fn parse_brace_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        // {
+        let start = self.curr_token.span;
+        if self.next_token.token_type == token::RBRACE {
+            return self.parse_hash_literal(start, None);
+        }
+
+        // the first statement is parsed before it is known to be in a block,
+        // so it gets the scope of the block in advance
+        self.scopes.push(HashMap::new());
+        let expr = self.parse_brace_content(start);
+        self.scopes.pop();
+        expr
+    }
+
+    fn parse_brace_content(&mut self, start: Span) -> Result<ast::Expression, ParseError> {
+        self.next_token();
+        let statement_only = match self.curr_token.token_type {
+            token::LET
+            | token::CONST
+            | token::RETURN
+            | token::WHILE
+            | token::FOR
+            | token::BREAK
+            | token::CONTINUE
+            | token::THROW
+            | token::DEFER => true,
+            token::FUNC => self.next_token.token_type == token::IDENT,
+            _ => false,
+        };
+        if statement_only {
+            let stmt = self.parse_statement()?;
+            return self.parse_block_expression(start, stmt);
+        }
+
+        let first_token = self.curr_token.token_type;
+        let first_start = self.curr_token.span;
+        let first = self.parse_expression(Priority::Lowest)?;
+
+        if self.next_token.token_type == token::COLON {
             self.next_token();
-            params.push(ast::Identifier {
-                token: token::IDENT,
-                value: self.curr_token.literal.clone(),
-            });
+            // label: while / label: for is a loop in a block, any other key: a hash
+            if let ast::Expression::Ident(label) = &first {
+                if [token::WHILE, token::FOR].contains(&self.next_token.token_type) {
+                    let label = label.value.clone();
+                    self.next_token();
+                    let stmt = self.parse_labeled_loop(label, first_start)?;
+                    return self.parse_block_expression(start, stmt);
+                }
+            }
+            return self.parse_hash_literal(start, Some(first));
         }
 
-        if !self.expect_next(token::RPAREN) {
-            // error
-            return vec![];
+        self.next_if(token::SEMICOLON);
+        let stmt = ast::Statement::Expr {
+            token:      first_token,
+            expression: first,
+            span:       first_start.to(self.curr_token.span),
+        };
+        self.parse_block_expression(start, stmt)
+    }
+
+    // the first key and its colon are already parsed when they told the hash from a block
+    fn parse_hash_literal(
+        &mut self,
+        start: Span,
+        first_key: Option<ast::Expression>,
+    ) -> Result<ast::Expression, ParseError> {
+        let mut pairs = vec![];
+        let mut first_key = first_key;
+        loop {
+            let key = match first_key.take() {
+                Some(key) => key,
+                None if self.next_token.token_type == token::RBRACE => break,
+                None => {
+                    self.next_token();
+                    let key = self.parse_expression(Priority::Lowest)?;
+                    self.expect_next(token::COLON)?;
+                    key
+                }
+            };
+            self.next_token();
+            let value = self.parse_expression(Priority::Lowest)?;
+            pairs.push((key, value));
+
+            if !self.next_if(token::COMMA) {
+                break;
+            }
         }
+        self.expect_next(token::RBRACE)?;
 
-        params
+        Ok(ast::Expression::HashLiteral {
+            token: token::LBRACE,
+            pairs,
+            span: start.to(self.curr_token.span),
+        })
     }
 
-    pub(super) fn parse_function_arguments(&mut self) -> Vec<ast::Expression> {
-        let mut args = vec![];
+    fn parse_block_expression(
+        &mut self,
+        start: Span,
+        first: ast::Statement,
+    ) -> Result<ast::Expression, ParseError> {
+        let mut statements = vec![first];
+        self.next_token();
+        statements.extend(self.parse_block_statement()?);
+
+        Ok(ast::Expression::Block {
+            token: token::LBRACE,
+            statements,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    pub(super) fn parse_index_expression(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        // [
+        let token = self.curr_token.token_type;
+        self.next_token();
+
+        let index = Box::new(self.parse_expression(Priority::Lowest)?);
+        self.expect_next(token::RBRACKET)?;
+
+        Ok(ast::Expression::Index {
+            token,
+            span: left.span().to(self.curr_token.span),
+            left: Box::new(left),
+            index,
+        })
+    }
+
+    // x? is postfix, there is nothing on its right
+    pub(super) fn parse_propagate_expression(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        Ok(ast::Expression::Propagate {
+            token: self.curr_token.token_type,
+            span:  left.span().to(self.curr_token.span),
+            value: Box::new(left),
+        })
+    }
+
+    //////////////////
+
+    pub(super) fn parse_function_parameters(&mut self) -> Result<Vec<ast::Parameter>, ParseError> {
+        let mut params = vec![];
 
         if self.curr_token.token_type == token::RPAREN {
+            return Ok(params);
+        }
+
+        params.push(self.parse_parameter()?);
+
+        while self.next_if(token::COMMA) {
             self.next_token();
-            return args;
+            params.push(self.parse_parameter()?);
         }
 
-        args.push(self.parse_expression(Priority::Lowest));
+        self.expect_next(token::RPAREN)?;
+        self.check_parameters(&params)?;
 
-        while self.next_token.token_type == token::COMMA {
+        Ok(params)
+    }
+
+    // x, x = default, [a, b] or ...x
+    fn parse_parameter(&mut self) -> Result<ast::Parameter, ParseError> {
+        let rest = self.curr_token.token_type == token::ELLIPSIS;
+        let pattern = if rest {
+            self.next_token();
+            ast::Pattern::Ident(self.parse_pattern_ident()?)
+        }
+        else {
+            self.parse_pattern()?
+        };
+
+        let mut default = None;
+        if self.next_token.token_type == token::ASSIGN {
+            if rest {
+                return Err(ParseError::new(
+                    error::INVALID_PARAMETER,
+                    format!("Rest parameter '{}' can not have a default value", pattern),
+                    &self.next_token,
+                ));
+            }
             self.next_token();
             self.next_token();
-            args.push(self.parse_expression(Priority::Lowest));
+            default = Some(self.parse_expression(Priority::Lowest)?);
         }
 
-        if !self.expect_next(token::RPAREN) {
-            // error
-            return vec![];
+        Ok(ast::Parameter {
+            pattern,
+            default,
+            rest,
+        })
+    }
+
+    // names are unique and a rest parameter comes last
+    fn check_parameters(&self, parameters: &[ast::Parameter]) -> Result<(), ParseError> {
+        let names = parameters
+            .iter()
+            .flat_map(|param| param.pattern.names())
+            .collect::<Vec<&ast::Identifier>>();
+        let (message, span) = if let Some(name) = duplicate_name(&names) {
+            (format!("Duplicate parameter '{}'", name.value), name.span)
+        }
+        else if let Some(param) = parameters.iter().rev().skip(1).find(|param| param.rest) {
+            (
+                format!("Rest parameter '{}' must be the last one", param.pattern),
+                param.pattern.span(),
+            )
         }
+        else {
+            return Ok(());
+        };
 
-        args
+        let mut err = ParseError::new(error::INVALID_PARAMETER, message, &self.curr_token);
+        err.span = span;
+        Err(err)
     }
+
+    // comma separated expressions closed by end, like function arguments and array elements
+    pub(super) fn parse_expression_list(
+        &mut self,
+        end: TokenType,
+    ) -> Result<Vec<ast::Expression>, ParseError> {
+        let mut list = vec![];
+
+        if self.curr_token.token_type == end {
+            return Ok(list);
+        }
+
+        list.push(self.parse_expression(Priority::Lowest)?);
+
+        while self.next_if(token::COMMA) {
+            self.next_token();
+            list.push(self.parse_expression(Priority::Lowest)?);
+        }
+
+        self.expect_next(end)?;
+
+        Ok(list)
+    }
+}
+
+// the pattern an expression reads as, like [a, {"k": b}] for a parameter of an arrow function
+fn expression_pattern(expression: &ast::Expression) -> Option<ast::Pattern> {
+    match expression {
+        ast::Expression::Ident(ident) => Some(ast::Pattern::Ident(ident.clone())),
+        ast::Expression::ArrayLiteral {
+            token,
+            elements,
+            span,
+        } => Some(ast::Pattern::Array {
+            token,
+            elements: elements
+                .iter()
+                .map(expression_pattern)
+                .collect::<Option<Vec<ast::Pattern>>>()?,
+            rest: None,
+            span: *span,
+        }),
+        ast::Expression::HashLiteral { token, pairs, span } => Some(ast::Pattern::Hash {
+            token,
+            pairs: pairs
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        ast::Expression::Ident(ident) => ident.value.clone(),
+                        ast::Expression::StringLiteral { value, .. } => value.clone(),
+                        _ => return None,
+                    };
+                    Some((key, expression_pattern(value)?))
+                })
+                .collect::<Option<Vec<(String, ast::Pattern)>>>()?,
+            span: *span,
+        }),
+        _ => None,
+    }
+}
+
+// value of an INT literal, None when it does not fit in u64
+fn integer_magnitude(literal: &str) -> Option<u64> {
+    let literal = literal.replace('_', "");
+    let (digits, radix) = match literal.get(..2) {
+        Some("0x" | "0X") => (&literal[2..], 16),
+        Some("0o" | "0O") => (&literal[2..], 8),
+        Some("0b" | "0B") => (&literal[2..], 2),
+        _ => (&literal[..], 10),
+    };
+    u64::from_str_radix(digits, radix).ok()
 }
@@ -1,23 +1,33 @@
 use std::collections::HashMap;
 
-use ast;
 use lexer::Lexer;
 use token::{Token, TokenType};
 
+mod error;
 mod expression;
+mod pattern;
 mod statement;
+pub use error::*;
 use expression::{InfixParseFn, PrefixParseFn};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Priority {
     Lowest,
     Assign,
+    Or,
+    And,
     Equal,
     Compare,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Sum,
     Product,
     Prefix,
+    Power,
     Call,
+    Index,
 }
 
 pub struct Parser {
@@ -25,15 +35,22 @@ pub struct Parser {
     curr_token: Token,
     next_token: Token,
 
-    // todo errors
-    // errors:     Vec<String>,
+    errors:             Vec<ParseError>,
+    // labels of the loops around the current statement, inside the current function
+    loops:              Vec<Option<String>>,
+    // how many functions the current statement is in
+    functions:          usize,
+    // names declared in each block around the current statement, true for const
+    scopes:             Vec<HashMap<String, bool>>,
+    // true in the pattern and guard of a match arm, where => ends them instead of being an arrow
+    arm_head:           bool,
     precedences:        HashMap<TokenType, Priority>,
     prefix_parse_funcs: HashMap<TokenType, PrefixParseFn>,
     infix_parse_funcs:  HashMap<TokenType, InfixParseFn>,
 }
 
 impl Parser {
-    pub fn parse(input: String) -> ast::Program {
+    pub fn parse(input: String) -> Result<ast::Program, Vec<ParseError>> {
         let mut l = Lexer::new(input);
 
         let ctoken = l.next_token();
@@ -43,18 +60,41 @@ impl Parser {
             lexer:              l,
             curr_token:         ctoken,
             next_token:         ntoken,
-            // errors:     vec![],
+            errors:             vec![],
+            loops:              vec![],
+            functions:          0,
+            scopes:             vec![HashMap::new()],
+            arm_head:           false,
             precedences:        HashMap::from([
                 (token::ASSIGN, Priority::Assign),
+                (token::PLUS_ASSIGN, Priority::Assign),
+                (token::MINUS_ASSIGN, Priority::Assign),
+                (token::ASTERISK_ASSIGN, Priority::Assign),
+                (token::SLASH_ASSIGN, Priority::Assign),
+                (token::PERCENT_ASSIGN, Priority::Assign),
+                (token::OR, Priority::Or),
+                (token::AND, Priority::And),
                 (token::EQ, Priority::Equal),
                 (token::NEQ, Priority::Equal),
                 (token::LT, Priority::Compare),
                 (token::GT, Priority::Compare),
+                (token::LTE, Priority::Compare),
+                (token::GTE, Priority::Compare),
+                (token::BIT_OR, Priority::BitOr),
+                (token::BIT_XOR, Priority::BitXor),
+                (token::BIT_AND, Priority::BitAnd),
+                (token::SHL, Priority::Shift),
+                (token::SHR, Priority::Shift),
                 (token::PLUS, Priority::Sum),
                 (token::MINUS, Priority::Sum),
                 (token::SLASH, Priority::Product),
                 (token::ASTERISK, Priority::Product),
+                (token::PERCENT, Priority::Product),
+                (token::POWER, Priority::Power),
                 (token::LPAREN, Priority::Call),
+                (token::ARROW, Priority::Call),
+                (token::QUESTION, Priority::Call),
+                (token::LBRACKET, Priority::Index),
             ]),
             prefix_parse_funcs: HashMap::new(),
             infix_parse_funcs:  HashMap::new(),
@@ -62,28 +102,60 @@ impl Parser {
 
         p.register_prefix(token::IDENT, Parser::parse_ident);
         p.register_prefix(token::INT, Parser::parse_integer_literal);
+        p.register_prefix(token::FLOAT, Parser::parse_float_literal);
+        p.register_prefix(token::STRING, Parser::parse_string_literal);
         p.register_prefix(token::BANG, Parser::parse_prefix_expression);
         p.register_prefix(token::MINUS, Parser::parse_prefix_expression);
+        p.register_prefix(token::BIT_NOT, Parser::parse_prefix_expression);
 
         p.register_infix(token::PLUS, Parser::parse_infix_expression);
         p.register_infix(token::MINUS, Parser::parse_infix_expression);
         p.register_infix(token::ASTERISK, Parser::parse_infix_expression);
         p.register_infix(token::SLASH, Parser::parse_infix_expression);
+        p.register_infix(token::PERCENT, Parser::parse_infix_expression);
+        p.register_infix(token::POWER, Parser::parse_infix_expression);
         p.register_infix(token::LT, Parser::parse_infix_expression);
         p.register_infix(token::GT, Parser::parse_infix_expression);
+        p.register_infix(token::LTE, Parser::parse_infix_expression);
+        p.register_infix(token::GTE, Parser::parse_infix_expression);
         p.register_infix(token::EQ, Parser::parse_infix_expression);
         p.register_infix(token::NEQ, Parser::parse_infix_expression);
-        p.register_infix(token::ASSIGN, Parser::parse_infix_expression);
+        p.register_infix(token::AND, Parser::parse_infix_expression);
+        p.register_infix(token::OR, Parser::parse_infix_expression);
+        p.register_infix(token::BIT_AND, Parser::parse_infix_expression);
+        p.register_infix(token::BIT_OR, Parser::parse_infix_expression);
+        p.register_infix(token::BIT_XOR, Parser::parse_infix_expression);
+        p.register_infix(token::SHL, Parser::parse_infix_expression);
+        p.register_infix(token::SHR, Parser::parse_infix_expression);
+        p.register_infix(token::ASSIGN, Parser::parse_assign_expression);
+        p.register_infix(token::PLUS_ASSIGN, Parser::parse_assign_expression);
+        p.register_infix(token::MINUS_ASSIGN, Parser::parse_assign_expression);
+        p.register_infix(token::ASTERISK_ASSIGN, Parser::parse_assign_expression);
+        p.register_infix(token::SLASH_ASSIGN, Parser::parse_assign_expression);
+        p.register_infix(token::PERCENT_ASSIGN, Parser::parse_assign_expression);
 
         p.register_prefix(token::TRUE, Parser::parse_boolean);
         p.register_prefix(token::FALSE, Parser::parse_boolean);
 
         p.register_prefix(token::LPAREN, Parser::parse_grouped_expression);
         p.register_prefix(token::IF, Parser::parse_if_expression);
+        p.register_prefix(token::MATCH, Parser::parse_match_expression);
+        p.register_prefix(token::TRY, Parser::parse_try_expression);
         p.register_prefix(token::FUNC, Parser::parse_function_literal);
         p.register_infix(token::LPAREN, Parser::parse_function_call_expression);
+        p.register_infix(token::ARROW, Parser::parse_arrow_function);
+        p.register_prefix(token::LBRACKET, Parser::parse_array_literal);
+        p.register_prefix(token::LBRACE, Parser::parse_brace_expression);
+        p.register_infix(token::LBRACKET, Parser::parse_index_expression);
+        p.register_infix(token::QUESTION, Parser::parse_propagate_expression);
 
-        p.parse_program()
+        let program = p.parse_program();
+        if p.errors.is_empty() {
+            Ok(program)
+        }
+        else {
+            Err(p.errors)
+        }
     }
 
     fn next_token(&mut self) {
@@ -91,24 +163,114 @@ impl Parser {
         self.next_token = self.lexer.next_token();
     }
 
-    fn expect_next(&mut self, expected_type: token::TokenType) -> bool {
+    fn expect_next(&mut self, expected_type: token::TokenType) -> Result<(), ParseError> {
+        if self.next_if(expected_type) {
+            Ok(())
+        }
+        else {
+            Err(ParseError::unexpected(
+                error::UNEXPECTED_TOKEN,
+                expected_type,
+                &self.next_token,
+            ))
+        }
+    }
+
+    // for optional tokens like semicolon
+    fn next_if(&mut self, expected_type: token::TokenType) -> bool {
         if self.next_token.token_type == expected_type {
             self.next_token();
             true
         }
         else {
-            // if error append errros
             false
         }
     }
+
+    // reads tokens after next_token without moving the parser
+    fn scan_ahead<T>(&mut self, scan: impl FnOnce(&mut Lexer) -> T) -> T {
+        let lexer = &mut self.lexer;
+        let saved = (
+            lexer.position,
+            lexer.ch,
+            lexer.line,
+            lexer.column,
+            lexer.offset,
+        );
+        let rlt = scan(lexer);
+        (
+            lexer.position,
+            lexer.ch,
+            lexer.line,
+            lexer.column,
+            lexer.offset,
+        ) = saved;
+        rlt
+    }
+
+    // panic mode: skip the rest of a broken statement,
+    // stopping on its semicolon or just before the next statement / closing brace
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.curr_token.token_type {
+                token::EOF => return,
+                token::RBRACE if depth == 0 => return,
+                token::SEMICOLON if depth == 0 => return,
+                token::LBRACE => depth += 1,
+                token::RBRACE => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                match self.next_token.token_type {
+                    token::RBRACE
+                    | token::EOF
+                    | token::LET
+                    | token::CONST
+                    | token::RETURN
+                    | token::WHILE
+                    | token::FOR
+                    | token::BREAK
+                    | token::CONTINUE
+                    | token::THROW
+                    | token::DEFER => return,
+                    _ => {}
+                }
+            }
+            self.next_token();
+        }
+    }
+}
+
+impl Parser {
+    fn declare(&mut self, name: &str, constant: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), constant);
+        }
+    }
+
+    // whether the nearest declaration of the name is a const
+    fn is_const(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .unwrap_or(false)
+    }
 }
 
 impl Parser {
     fn parse_program(&mut self) -> ast::Program {
         let mut program = ast::Program { statements: vec![] };
         while self.curr_token.token_type != token::EOF {
-            let stmt = self.parse_statement();
-            program.statements.push(stmt);
+            match self.parse_statement() {
+                Ok(stmt) => program.statements.push(stmt),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.synchronize();
+                }
+            }
             self.next_token();
         }
         program
@@ -0,0 +1,187 @@
+use super::error::{self, ParseError};
+use super::Parser;
+use super::Priority;
+
+impl Parser {
+    // x, [a, b, ...rest] or {name, age: years}, starting at the current token.
+    // a match arm can also have _ and literals like 1 or "a", which may not match
+    pub(super) fn parse_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        match self.curr_token.token_type {
+            token::IDENT if self.arm_head && self.curr_token.literal == "_" => {
+                Ok(ast::Pattern::Wildcard(self.curr_token.span))
+            }
+            token::IDENT => Ok(ast::Pattern::Ident(self.parse_pattern_ident()?)),
+            token::INT
+            | token::FLOAT
+            | token::STRING
+            | token::TRUE
+            | token::FALSE
+            | token::MINUS
+                if self.arm_head =>
+            {
+                self.parse_literal_pattern()
+            }
+            token::LBRACKET => self.parse_array_pattern(),
+            token::LBRACE => self.parse_hash_pattern(),
+            _ => Err(ParseError::unexpected(
+                error::UNEXPECTED_TOKEN,
+                token::IDENT,
+                &self.curr_token,
+            )),
+        }
+    }
+
+    // a pattern can not bind the same name twice, like let [a, a] = ..
+    pub(super) fn check_pattern(&self, pattern: &ast::Pattern) -> Result<(), ParseError> {
+        match duplicate_name(&pattern.names()) {
+            Some(name) => {
+                let mut err = ParseError::new(
+                    error::INVALID_PATTERN,
+                    format!("'{}' is bound more than once", name.value),
+                    &self.curr_token,
+                );
+                err.span = name.span;
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub(super) fn parse_pattern_ident(&mut self) -> Result<ast::Identifier, ParseError> {
+        if self.curr_token.token_type != token::IDENT {
+            return Err(ParseError::unexpected(
+                error::UNEXPECTED_TOKEN,
+                token::IDENT,
+                &self.curr_token,
+            ));
+        }
+
+        Ok(ast::Identifier {
+            token: token::IDENT,
+            value: self.curr_token.literal.clone(),
+            span:  self.curr_token.span,
+        })
+    }
+
+    // a number, which can be negative, a string or a bool
+    fn parse_literal_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        let literal = self.parse_expression(Priority::Prefix)?;
+        let valid = match &literal {
+            ast::Expression::IntegerLiteral { .. }
+            | ast::Expression::FloatLiteral { .. }
+            | ast::Expression::StringLiteral { .. }
+            | ast::Expression::Bool { .. } => true,
+            ast::Expression::Prefix {
+                operator, right, ..
+            } => {
+                operator == "-"
+                    && matches!(
+                        **right,
+                        ast::Expression::IntegerLiteral { .. }
+                            | ast::Expression::FloatLiteral { .. }
+                    )
+            }
+            _ => false,
+        };
+        if !valid {
+            let mut err = ParseError::new(
+                error::INVALID_PATTERN,
+                format!("Invalid pattern {}", literal),
+                &self.curr_token,
+            );
+            err.span = literal.span();
+            return Err(err);
+        }
+        Ok(ast::Pattern::Literal(literal))
+    }
+
+    fn parse_array_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        // [
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        let mut elements = vec![];
+        let mut rest = None;
+        if !self.next_if(token::RBRACKET) {
+            loop {
+                self.next_token();
+                // ...rest takes what is left and closes the pattern
+                if self.curr_token.token_type == token::ELLIPSIS {
+                    self.next_token();
+                    rest = Some(self.parse_pattern_ident()?);
+                    self.expect_next(token::RBRACKET)?;
+                    break;
+                }
+                elements.push(self.parse_pattern()?);
+                if !self.next_if(token::COMMA) {
+                    self.expect_next(token::RBRACKET)?;
+                    break;
+                }
+            }
+        }
+
+        Ok(ast::Pattern::Array {
+            token,
+            elements,
+            rest,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    fn parse_hash_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        // {
+        let token = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        let mut pairs = vec![];
+        if !self.next_if(token::RBRACE) {
+            loop {
+                self.next_token();
+                // key: pattern, a bare key binds the ident of the same name
+                let key = self.curr_token.literal.clone();
+                let token_type = self.curr_token.token_type;
+                let pattern = match token_type {
+                    token::IDENT | token::STRING if self.next_if(token::COLON) => {
+                        self.next_token();
+                        self.parse_pattern()?
+                    }
+                    token::IDENT => ast::Pattern::Ident(self.parse_pattern_ident()?),
+                    token::STRING => {
+                        return Err(ParseError::unexpected(
+                            error::UNEXPECTED_TOKEN,
+                            token::COLON,
+                            &self.next_token,
+                        ))
+                    }
+                    _ => {
+                        return Err(ParseError::unexpected(
+                            error::UNEXPECTED_TOKEN,
+                            token::IDENT,
+                            &self.curr_token,
+                        ))
+                    }
+                };
+                pairs.push((key, pattern));
+                if !self.next_if(token::COMMA) {
+                    self.expect_next(token::RBRACE)?;
+                    break;
+                }
+            }
+        }
+
+        Ok(ast::Pattern::Hash {
+            token,
+            pairs,
+            span: start.to(self.curr_token.span),
+        })
+    }
+}
+
+// the first name which is bound twice
+pub(super) fn duplicate_name<'a>(names: &[&'a ast::Identifier]) -> Option<&'a ast::Identifier> {
+    names
+        .iter()
+        .enumerate()
+        .find(|(i, name)| names[..*i].iter().any(|other| other.value == name.value))
+        .map(|(_, name)| *name)
+}
@@ -1,72 +1,343 @@
+use super::error::{self, ParseError};
 use super::Parser;
 use super::Priority;
+use std::collections::HashMap;
+use token::Span;
 
 impl Parser {
-    pub(super) fn parse_statement(&mut self) -> ast::Statement {
+    pub(super) fn parse_statement(&mut self) -> Result<ast::Statement, ParseError> {
         match self.curr_token.token_type {
-            token::LET => self.parse_let_statement(),
-            token::RETURN => self.parse_return_statement(),
+            token::LET | token::CONST => self.parse_let_statement(),
+            token::FUNC if self.next_token.token_type == token::IDENT => {
+                self.parse_function_statement()
+            }
+            token::RETURN | token::THROW => self.parse_return_statement(),
+            token::WHILE => self.parse_while_statement(None, self.curr_token.span),
+            token::FOR => self.parse_for_statement(None, self.curr_token.span),
+            token::BREAK | token::CONTINUE => self.parse_jump_statement(),
+            token::DEFER => self.parse_defer_statement(),
+            token::IDENT if self.next_token.token_type == token::COLON => {
+                self.parse_labeled_statement()
+            }
             _ => self.parse_expr_statement(),
         }
     }
 
-    pub(super) fn parse_let_statement(&mut self) -> ast::Statement {
+    // let x = ..; or const [a, b] = ..;
+    pub(super) fn parse_let_statement(&mut self) -> Result<ast::Statement, ParseError> {
         let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
 
-        if !self.expect_next(token::IDENT) {
-            panic!("PAR0001: No ident after LET");
-            // if error return null
+        if ![token::IDENT, token::LBRACKET, token::LBRACE].contains(&self.next_token.token_type) {
+            return Err(ParseError::unexpected(
+                error::NO_IDENT_AFTER_LET,
+                token::IDENT,
+                &self.next_token,
+            ));
         }
+        self.next_token();
+        let pattern = self.parse_pattern()?;
+        self.check_pattern(&pattern)?;
+
+        if !self.next_if(token::ASSIGN) {
+            return Err(ParseError::unexpected(
+                error::NO_ASSIGN_AFTER_IDENT,
+                token::ASSIGN,
+                &self.next_token,
+            ));
+        }
+        self.next_token();
+
+        let value = self.parse_expression(Priority::Lowest)?;
+        self.next_if(token::SEMICOLON);
+
+        let span = start.to(self.curr_token.span);
+        let constant = token_type == token::CONST;
+        for name in pattern.names() {
+            self.declare(&name.value, constant);
+        }
+        if constant {
+            Ok(ast::Statement::Const {
+                token: token_type,
+                pattern,
+                value,
+                span,
+            })
+        }
+        else {
+            Ok(ast::Statement::Let {
+                token: token_type,
+                pattern,
+                value,
+                span,
+            })
+        }
+    }
 
-        let var_name = ast::Identifier {
+    // fn name(params) { body }
+    pub(super) fn parse_function_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        self.next_token();
+
+        let name = ast::Identifier {
             token: self.curr_token.token_type,
             value: self.curr_token.literal.clone(),
+            span:  self.curr_token.span,
         };
+        self.declare(&name.value, false);
+
+        let (parameters, body) = self.parse_function_rest()?;
+        self.next_if(token::SEMICOLON);
 
-        if !self.expect_next(token::ASSIGN) {
-            panic!("PAR0002: No ASSIGN sign after ident");
-            // if error return null
+        Ok(ast::Statement::Function {
+            token: token_type,
+            name,
+            parameters,
+            body,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    // return ..; or throw ..;
+    pub(super) fn parse_return_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        self.next_token();
+
+        let value = self.parse_expression(Priority::Lowest)?;
+        self.next_if(token::SEMICOLON);
+
+        let span = start.to(self.curr_token.span);
+        if token_type == token::THROW {
+            Ok(ast::Statement::Throw {
+                token: token_type,
+                value,
+                span,
+            })
+        }
+        else {
+            Ok(ast::Statement::Return {
+                token: token_type,
+                value,
+                span,
+            })
+        }
+    }
+
+    // defer ..; runs .. when the function around it returns
+    pub(super) fn parse_defer_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        if self.functions == 0 {
+            return Err(ParseError::new(
+                error::OUTSIDE_OF_FUNCTION,
+                "'defer' outside of a function".to_string(),
+                &self.curr_token,
+            ));
         }
         self.next_token();
 
-        let value = self.parse_expression(Priority::Lowest);
-        self.expect_next(token::SEMICOLON);
+        let value = self.parse_expression(Priority::Lowest)?;
+        self.next_if(token::SEMICOLON);
 
-        let stmt = ast::Statement::Let {
+        Ok(ast::Statement::Defer {
             token: token_type,
-            name: var_name,
             value,
-        };
+            span: start.to(self.curr_token.span),
+        })
+    }
 
-        stmt
+    pub(super) fn parse_expr_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
+        let expr = self.parse_expression(Priority::Lowest)?;
+
+        self.next_if(token::SEMICOLON);
+
+        Ok(ast::Statement::Expr {
+            token:      token_type,
+            expression: expr,
+            span:       start.to(self.curr_token.span),
+        })
     }
 
-    pub(super) fn parse_return_statement(&mut self) -> ast::Statement {
+    // label: while (...) { } or label: for (...) { }
+    pub(super) fn parse_labeled_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let label = self.curr_token.literal.clone();
+        let start = self.curr_token.span;
+        self.next_token();
+        self.next_token();
+        self.parse_labeled_loop(label, start)
+    }
+
+    // the loop after label:, starting at its while or for
+    pub(super) fn parse_labeled_loop(
+        &mut self,
+        label: String,
+        start: Span,
+    ) -> Result<ast::Statement, ParseError> {
+        match self.curr_token.token_type {
+            token::WHILE => self.parse_while_statement(Some(label), start),
+            token::FOR => self.parse_for_statement(Some(label), start),
+            _ => Err(ParseError::new(
+                error::LABEL_WITHOUT_LOOP,
+                format!("Label '{}' must be followed by a loop", label),
+                &self.curr_token,
+            )),
+        }
+    }
+
+    // start is where the label is, if there is one
+    pub(super) fn parse_while_statement(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> Result<ast::Statement, ParseError> {
+        // while
         let token_type = self.curr_token.token_type;
+
+        // condition, the parentheses around it are optional
         self.next_token();
+        let condition = self.parse_expression(Priority::Lowest)?;
 
-        let value = self.parse_expression(Priority::Lowest);
-        self.expect_next(token::SEMICOLON);
+        // { body }
+        let body = self.parse_loop_body(&label)?;
+        self.next_if(token::SEMICOLON);
 
-        let stmt = ast::Statement::Return {
+        Ok(ast::Statement::While {
             token: token_type,
-            value,
-        };
+            label,
+            condition,
+            body,
+            span: start.to(self.curr_token.span),
+        })
+    }
 
+    pub(super) fn parse_for_statement(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> Result<ast::Statement, ParseError> {
+        // what init declares belongs to the loop
+        self.scopes.push(HashMap::new());
+        let stmt = self.parse_for_loop(label, start);
+        self.scopes.pop();
         stmt
     }
 
-    pub(super) fn parse_expr_statement(&mut self) -> ast::Statement {
+    // every part of (init; condition; step) can be left out
+    fn parse_for_loop(
+        &mut self,
+        label: Option<String>,
+        start: Span,
+    ) -> Result<ast::Statement, ParseError> {
+        // for
         let token_type = self.curr_token.token_type;
-        let expr = self.parse_expression(Priority::Lowest);
+        self.expect_next(token::LPAREN)?;
 
-        self.expect_next(token::SEMICOLON);
+        // init;
+        let mut init = None;
+        if !self.next_if(token::SEMICOLON) {
+            self.next_token();
+            let stmt = match self.curr_token.token_type {
+                token::LET | token::CONST => self.parse_let_statement()?,
+                _ => self.parse_expr_statement()?,
+            };
+            if self.curr_token.token_type != token::SEMICOLON {
+                return Err(ParseError::unexpected(
+                    error::UNEXPECTED_TOKEN,
+                    token::SEMICOLON,
+                    &self.next_token,
+                ));
+            }
+            init = Some(Box::new(stmt));
+        }
 
-        let stmt = ast::Statement::Expr {
-            token:      token_type,
-            expression: expr,
-        };
+        // condition;
+        let mut condition = None;
+        if !self.next_if(token::SEMICOLON) {
+            self.next_token();
+            condition = Some(self.parse_expression(Priority::Lowest)?);
+            self.expect_next(token::SEMICOLON)?;
+        }
 
-        stmt
+        // step)
+        let mut step = None;
+        if !self.next_if(token::RPAREN) {
+            self.next_token();
+            step = Some(self.parse_expression(Priority::Lowest)?);
+            self.expect_next(token::RPAREN)?;
+        }
+
+        // { body }
+        let body = self.parse_loop_body(&label)?;
+        self.next_if(token::SEMICOLON);
+
+        Ok(ast::Statement::For {
+            token: token_type,
+            label,
+            init,
+            condition,
+            step,
+            body,
+            span: start.to(self.curr_token.span),
+        })
+    }
+
+    fn parse_loop_body(
+        &mut self,
+        label: &Option<String>,
+    ) -> Result<Vec<ast::Statement>, ParseError> {
+        self.expect_next(token::LBRACE)?;
+        self.next_token();
+
+        self.loops.push(label.clone());
+        let body = self.parse_block_statement();
+        self.loops.pop();
+        body
+    }
+
+    // break; continue; break label; continue label;
+    pub(super) fn parse_jump_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let token_type = self.curr_token.token_type;
+        let start = self.curr_token.span;
+
+        if self.loops.is_empty() {
+            return Err(ParseError::new(
+                error::OUTSIDE_OF_LOOP,
+                format!("'{}' outside of a loop", self.curr_token.literal),
+                &self.curr_token,
+            ));
+        }
+
+        let mut label = None;
+        if self.next_if(token::IDENT) {
+            let name = self.curr_token.literal.clone();
+            if !self.loops.contains(&Some(name.clone())) {
+                return Err(ParseError::new(
+                    error::UNDEFINED_LABEL,
+                    format!("Undefined loop label '{}'", name),
+                    &self.curr_token,
+                ));
+            }
+            label = Some(name);
+        }
+        self.next_if(token::SEMICOLON);
+
+        let span = start.to(self.curr_token.span);
+        Ok(match token_type {
+            token::BREAK => ast::Statement::Break {
+                token: token_type,
+                label,
+                span,
+            },
+            _ => ast::Statement::Continue {
+                token: token_type,
+                label,
+                span,
+            },
+        })
     }
 }
@@ -1,9 +1,24 @@
 #[cfg(test)]
 mod parser_tests {
-    use ast;
-    use ast::Identifier;
-    use parser;
-    use parser::Parser;
+    use ast::{Identifier, Parameter, Pattern, Span};
+    use parser::{ParseError, Parser};
+
+    // the errors parsing input gives, checked by (code, line, column)
+    fn assert_parse_errors(input: String, expected: &[(&str, usize, usize)]) -> Vec<ParseError> {
+        let errors = match Parser::parse(input) {
+            Ok(_) => panic!("Not a parse error"),
+            Err(errors) => errors,
+        };
+
+        assert_eq!(errors.len(), expected.len());
+
+        for (error, exp) in errors.iter().zip(expected) {
+            assert_eq!(error.code, exp.0);
+            assert_eq!(error.span.line, exp.1);
+            assert_eq!(error.span.column, exp.2);
+        }
+        errors
+    }
 
     #[test]
     fn test_let() {
@@ -16,7 +31,7 @@ mod parser_tests {
 
         let expected_ident: Vec<&str> = vec!["five", "ten", "result"];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), expected_ident.len());
 
@@ -26,8 +41,9 @@ mod parser_tests {
             match stmt {
                 ast::Statement::Let {
                     token,
-                    name,
+                    pattern: ast::Pattern::Ident(name),
                     value: _,
+                    span: _,
                 } => {
                     assert_eq!(*token, token::LET);
                     assert_eq!(name.token, token::IDENT);
@@ -49,13 +65,17 @@ mod parser_tests {
         "
         .to_string();
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 5);
 
         for stmt in program.statements {
             match stmt {
-                ast::Statement::Return { token, value: _ } => {
+                ast::Statement::Return {
+                    token,
+                    value: _,
+                    span: _,
+                } => {
                     assert_eq!(token, token::RETURN);
                 }
                 _ => panic!("Not a Let statement"),
@@ -70,13 +90,17 @@ mod parser_tests {
         "
         .to_string();
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 1);
 
         for stmt in program.statements {
             match stmt {
-                ast::Statement::Expr { token, expression } => {
+                ast::Statement::Expr {
+                    token,
+                    expression,
+                    span: _,
+                } => {
                     assert_eq!(token, token::IDENT);
                     match expression {
                         ast::Expression::Ident(ident) => {
@@ -98,16 +122,24 @@ mod parser_tests {
         "
         .to_string();
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 1);
 
         for stmt in program.statements {
             match stmt {
-                ast::Statement::Expr { token, expression } => {
+                ast::Statement::Expr {
+                    token,
+                    expression,
+                    span: _,
+                } => {
                     assert_eq!(token, token::INT);
                     match expression {
-                        ast::Expression::IntegerLiteral { token, value } => {
+                        ast::Expression::IntegerLiteral {
+                            token,
+                            value,
+                            span: _,
+                        } => {
                             assert_eq!(token, token::INT);
                             assert_eq!(value, 12345);
                         }
@@ -119,6 +151,244 @@ mod parser_tests {
         }
     }
 
+    #[test]
+    fn test_integer_literal_radix() {
+        let input = "
+            1_000_000;
+            0x1F;
+            0o17;
+            0b1010;
+            9223372036854775807;
+            -9223372036854775808;
+            0x7FFF_FFFF_FFFF_FFFF;
+        "
+        .to_string();
+
+        let expected: Vec<i64> = vec![1_000_000, 31, 15, 10, i64::MAX, i64::MIN, i64::MAX];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            match &program.statements[i] {
+                ast::Statement::Expr { expression, .. } => match expression {
+                    ast::Expression::IntegerLiteral { value, .. } => assert_eq!(value, exp),
+                    _ => panic!("Not a Expression::IntegerLiteral"),
+                },
+                _ => panic!("Not a Expr Statement"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_integer_literal_out_of_range() {
+        let input = "
+            99999999999999999999;
+            9223372036854775808;
+            0x1_0000_0000_0000_0000;
+            let ok = 1;
+            0b102;
+        "
+        .to_string();
+
+        let errors = match Parser::parse(input) {
+            Ok(_) => panic!("Not a parse error"),
+            Err(errors) => errors,
+        };
+
+        let expected: Vec<&str> = vec!["PAR0006", "PAR0006", "PAR0006", "PAR0005"];
+
+        assert_eq!(errors.len(), expected.len());
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(errors[i].code, *exp);
+        }
+        assert_eq!(
+            errors[0].to_string(),
+            "PAR0006: Integer literal 99999999999999999999 is out of range for i64 (line 2, column 13)"
+        );
+    }
+
+    #[test]
+    fn test_float_literal_expression() {
+        let input = "
+            1.5;
+            2e10;
+            1_000.5e-3;
+            1.5 * 2;
+            1e400;
+        "
+        .to_string();
+
+        let errors = match Parser::parse(input.clone()) {
+            Ok(_) => panic!("Not a parse error"),
+            Err(errors) => errors,
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "PAR0007");
+        assert_eq!(errors[0].span.line, 6);
+
+        let input = input.replace("1e400;", "");
+        let expected: Vec<&str> = vec!["1.5", "20000000000.0", "1.0005", "(1.5 * 2)"];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        match &program.statements[0] {
+            ast::Statement::Expr { expression, .. } => assert_eq!(
+                *expression,
+                ast::Expression::FloatLiteral {
+                    token: token::FLOAT,
+                    value: 1.5,
+                    span:  Span::new(2, 13, 13, 16),
+                },
+            ),
+            _ => panic!("Not a Expr Statement"),
+        }
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+    }
+
+    #[test]
+    fn test_string_literal_expression() {
+        let input = "
+            \"hello world\";
+            \"a\" + \"b\";
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec!["\"hello world\"", "(\"a\" + \"b\")"];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        match &program.statements[0] {
+            ast::Statement::Expr { expression, .. } => assert_eq!(
+                *expression,
+                ast::Expression::StringLiteral {
+                    token: token::STRING,
+                    value: "hello world".to_owned(),
+                    span:  Span::new(2, 13, 13, 26),
+                },
+            ),
+            _ => panic!("Not a Expr Statement"),
+        }
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+    }
+
+    #[test]
+    fn test_array_literal_expression() {
+        let input = "
+            [1, 2 * 2, \"three\"];
+            [];
+            arr[1 + 1];
+        "
+        .to_string();
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), 3);
+
+        match &program.statements[0] {
+            ast::Statement::Expr { expression, .. } => match expression {
+                ast::Expression::ArrayLiteral {
+                    token, elements, ..
+                } => {
+                    assert_eq!(*token, token::LBRACKET);
+                    assert_eq!(elements.len(), 3);
+                    assert_eq!(elements[1].to_string(), "(2 * 2)");
+                }
+                _ => panic!("Not a Expression::ArrayLiteral"),
+            },
+            _ => panic!("Not a Expr Statement"),
+        }
+
+        assert_eq!(program.statements[1].to_string(), "[]");
+
+        match &program.statements[2] {
+            ast::Statement::Expr { expression, .. } => assert_eq!(
+                *expression,
+                ast::Expression::Index {
+                    token: token::LBRACKET,
+                    left:  Box::new(ast::Expression::Ident(ast::Identifier {
+                        token: token::IDENT,
+                        value: "arr".to_owned(),
+                        span:  Span::new(4, 13, 62, 65),
+                    })),
+                    index: Box::new(ast::Expression::Infix {
+                        token:    token::PLUS,
+                        left:     Box::new(ast::Expression::IntegerLiteral {
+                            token: token::INT,
+                            value: 1,
+                            span:  Span::new(4, 17, 66, 67),
+                        }),
+                        operator: "+".to_owned(),
+                        right:    Box::new(ast::Expression::IntegerLiteral {
+                            token: token::INT,
+                            value: 1,
+                            span:  Span::new(4, 21, 70, 71),
+                        }),
+                        span:     Span::new(4, 17, 66, 71),
+                    }),
+                    span:  Span::new(4, 13, 62, 72),
+                },
+            ),
+            _ => panic!("Not a Expr Statement"),
+        }
+    }
+
+    #[test]
+    fn test_hash_literal_expression() {
+        let input = "
+            {\"one\": 1, 2: 1 + 1, true: [3],};
+            {};
+            let f = fn(x) { {x: x} };
+            if (x) { {1: 2} } else { y };
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "{\"one\": 1, 2: (1 + 1), true: [3]}",
+            "{}",
+            "let f = fn(x) { {x: x} };",
+            "if(x) { {1: 2} } else { y }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        match &program.statements[0] {
+            ast::Statement::Expr { expression, .. } => match expression {
+                ast::Expression::HashLiteral { token, pairs, .. } => {
+                    assert_eq!(*token, token::LBRACE);
+                    assert_eq!(pairs.len(), 3);
+                    assert_eq!(
+                        pairs[0].0,
+                        ast::Expression::StringLiteral {
+                            token: token::STRING,
+                            value: "one".to_owned(),
+                            span:  Span::new(2, 14, 14, 19),
+                        },
+                    );
+                }
+                _ => panic!("Not a Expression::HashLiteral"),
+            },
+            _ => panic!("Not a Expr Statement"),
+        }
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+    }
+
     #[test]
     fn test_prefix_expression() {
         let input = "
@@ -136,7 +406,9 @@ mod parser_tests {
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 15,
+                    span:  Span::new(2, 14, 14, 16),
                 }),
+                span:     Span::new(2, 13, 13, 16),
             },
             ast::Expression::Prefix {
                 token:    token::BANG,
@@ -144,7 +416,9 @@ mod parser_tests {
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 5,
+                    span:  Span::new(3, 14, 31, 32),
                 }),
+                span:     Span::new(3, 13, 30, 32),
             },
             ast::Expression::Prefix {
                 token:    token::BANG,
@@ -152,7 +426,9 @@ mod parser_tests {
                 right:    Box::new(ast::Expression::Bool {
                     token: token::TRUE,
                     value: true,
+                    span:  Span::new(4, 14, 47, 51),
                 }),
+                span:     Span::new(4, 13, 46, 51),
             },
             ast::Expression::Prefix {
                 token:    token::BANG,
@@ -160,11 +436,13 @@ mod parser_tests {
                 right:    Box::new(ast::Expression::Bool {
                     token: token::FALSE,
                     value: false,
+                    span:  Span::new(5, 14, 66, 71),
                 }),
+                span:     Span::new(5, 13, 65, 71),
             },
         ];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 4);
 
@@ -175,6 +453,7 @@ mod parser_tests {
                 ast::Statement::Expr {
                     token: _,
                     expression,
+                    span: _,
                 } => {
                     assert_eq!(expression, exp);
                 }
@@ -203,100 +482,124 @@ mod parser_tests {
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(2, 13, 13, 14),
                 }),
                 operator: String::from("+"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(2, 17, 17, 18),
                 }),
+                span:     Span::new(2, 13, 13, 18),
             },
             ast::Expression::Infix {
                 token:    token::MINUS,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(3, 13, 32, 33),
                 }),
                 operator: String::from("-"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(3, 17, 36, 37),
                 }),
+                span:     Span::new(3, 13, 32, 37),
             },
             ast::Expression::Infix {
                 token:    token::ASTERISK,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(4, 13, 51, 52),
                 }),
                 operator: String::from("*"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(4, 17, 55, 56),
                 }),
+                span:     Span::new(4, 13, 51, 56),
             },
             ast::Expression::Infix {
                 token:    token::SLASH,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(5, 13, 70, 71),
                 }),
                 operator: String::from("/"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(5, 17, 74, 75),
                 }),
+                span:     Span::new(5, 13, 70, 75),
             },
             ast::Expression::Infix {
                 token:    token::LT,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(6, 13, 89, 90),
                 }),
                 operator: String::from("<"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(6, 17, 93, 94),
                 }),
+                span:     Span::new(6, 13, 89, 94),
             },
             ast::Expression::Infix {
                 token:    token::GT,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(7, 13, 108, 109),
                 }),
                 operator: String::from(">"),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(7, 17, 112, 113),
                 }),
+                span:     Span::new(7, 13, 108, 113),
             },
             ast::Expression::Infix {
                 token:    token::EQ,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(8, 13, 127, 128),
                 }),
                 operator: String::from("=="),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(8, 18, 132, 133),
                 }),
+                span:     Span::new(8, 13, 127, 133),
             },
             ast::Expression::Infix {
                 token:    token::NEQ,
                 left:     Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(9, 13, 147, 148),
                 }),
                 operator: String::from("!="),
                 right:    Box::new(ast::Expression::IntegerLiteral {
                     token: token::INT,
                     value: 1,
+                    span:  Span::new(9, 18, 152, 153),
                 }),
+                span:     Span::new(9, 13, 147, 153),
             },
         ];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 8);
 
@@ -307,6 +610,7 @@ mod parser_tests {
                 ast::Statement::Expr {
                     token: _,
                     expression,
+                    span: _,
                 } => {
                     assert_eq!(expression, exp);
                 }
@@ -336,6 +640,26 @@ mod parser_tests {
             !(true == !false);
             1 + add(1 * 1) + d;
             add(1, 2, 3 * 4, sub(5 + 6 * 7, 8), 10 * 11);
+            a * [1, 2, 3, 4][b * c] * d;
+            add(a * b[2], b[1], 2 * [1, 2][1]);
+            -arr[0];
+            a <= b == b >= c;
+            a % b * c ** d;
+            2 ** 3 ** 2;
+            -2 ** 2;
+            a || b && c || d;
+            !a && b == c;
+            a | b ^ c & d;
+            a << 1 + b >> c;
+            a & 1 == 0;
+            ~a & b;
+            a = b = c + 1;
+            a += b -= c * 2;
+            xs[i + 1] %= 2;
+            h[\"k\"][0] /= a || b;
+            -f(x)? + 1;
+            a[0]?[1];
+            x?? * 2;
         "
         .to_string();
 
@@ -358,9 +682,29 @@ mod parser_tests {
             "(!(true == (!false)))",
             "((1 + add((1 * 1))) + d)",
             "add(1, 2, (3 * 4), sub((5 + (6 * 7)), 8), (10 * 11))",
+            "((a * ([1, 2, 3, 4][(b * c)])) * d)",
+            "add((a * (b[2])), (b[1]), (2 * ([1, 2][1])))",
+            "(-(arr[0]))",
+            "((a <= b) == (b >= c))",
+            "((a % b) * (c ** d))",
+            "(2 ** (3 ** 2))",
+            "(-(2 ** 2))",
+            "((a || (b && c)) || d)",
+            "((!a) && (b == c))",
+            "(a | (b ^ (c & d)))",
+            "((a << (1 + b)) >> c)",
+            "((a & 1) == 0)",
+            "((~a) & b)",
+            "(a = (b = (c + 1)))",
+            "(a += (b -= (c * 2)))",
+            "((xs[(i + 1)]) %= 2)",
+            "(((h[\"k\"])[0]) /= (a || b))",
+            "((-(f(x)?)) + 1)",
+            "(((a[0])?)[1])",
+            "(((x?)?) * 2)",
         ];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), expected.len());
 
@@ -382,14 +726,16 @@ mod parser_tests {
             ast::Expression::Bool {
                 token: token::TRUE,
                 value: true,
+                span:  Span::new(2, 13, 13, 17),
             },
             ast::Expression::Bool {
                 token: token::FALSE,
                 value: false,
+                span:  Span::new(3, 13, 31, 36),
             },
         ];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 2);
 
@@ -399,6 +745,7 @@ mod parser_tests {
                 ast::Statement::Expr {
                     token: _,
                     expression,
+                    span: _,
                 } => {
                     assert_eq!(expression, exp);
                 }
@@ -421,24 +768,30 @@ mod parser_tests {
                 left:     Box::new(ast::Expression::Ident(ast::Identifier {
                     token: token::IDENT,
                     value: "x".to_owned(),
+                    span:  Span::new(2, 17, 17, 18),
                 })),
                 operator: "<".to_owned(),
                 right:    Box::new(ast::Expression::Ident(ast::Identifier {
                     token: token::IDENT,
                     value: "y".to_owned(),
+                    span:  Span::new(2, 21, 21, 22),
                 })),
+                span:     Span::new(2, 17, 17, 22),
             }),
             consequence: vec![ast::Statement::Expr {
                 token:      token::IDENT,
                 expression: ast::Expression::Ident(ast::Identifier {
                     token: token::IDENT,
                     value: "x".to_owned(),
+                    span:  Span::new(2, 26, 26, 27),
                 }),
+                span:       Span::new(2, 26, 26, 27),
             }],
             alternative: vec![],
+            span:        Span::new(2, 13, 13, 29),
         };
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 1);
 
@@ -447,6 +800,7 @@ mod parser_tests {
             ast::Statement::Expr {
                 token: _,
                 expression,
+                span: _,
             } => assert_eq!(*expression, expected),
             _ => panic!("Not a Expr Statement"),
         }
@@ -463,51 +817,93 @@ mod parser_tests {
         "
         .to_string();
 
-        let expected = ast::Expression::If {
-            token:       token::IF,
-            condition:   Box::new(ast::Expression::Infix {
-                token:    token::LT,
-                left:     Box::new(ast::Expression::Ident(ast::Identifier {
-                    token: token::IDENT,
-                    value: "x".to_owned(),
-                })),
-                operator: "<".to_owned(),
-                right:    Box::new(ast::Expression::Ident(ast::Identifier {
-                    token: token::IDENT,
-                    value: "y".to_owned(),
-                })),
-            }),
-            consequence: vec![ast::Statement::Expr {
-                token:      token::IDENT,
-                expression: ast::Expression::Ident(ast::Identifier {
-                    token: token::IDENT,
-                    value: "x".to_owned(),
-                }),
-            }],
-            alternative: vec![ast::Statement::Expr {
-                token:      token::IDENT,
-                expression: ast::Expression::Ident(ast::Identifier {
-                    token: token::IDENT,
-                    value: "y".to_owned(),
+        // every line holds the same expression, base is the offset of the line
+        let expected = |line: usize, base: usize| {
+            let span = |column: usize, end: usize| {
+                Span::new(line, column, base + column - 1, base + end - 1)
+            };
+            ast::Expression::If {
+                token:       token::IF,
+                condition:   Box::new(ast::Expression::Infix {
+                    token:    token::LT,
+                    left:     Box::new(ast::Expression::Ident(ast::Identifier {
+                        token: token::IDENT,
+                        value: "x".to_owned(),
+                        span:  span(17, 18),
+                    })),
+                    operator: "<".to_owned(),
+                    right:    Box::new(ast::Expression::Ident(ast::Identifier {
+                        token: token::IDENT,
+                        value: "y".to_owned(),
+                        span:  span(21, 22),
+                    })),
+                    span:     span(17, 22),
                 }),
-            }],
+                consequence: vec![ast::Statement::Expr {
+                    token:      token::IDENT,
+                    expression: ast::Expression::Ident(ast::Identifier {
+                        token: token::IDENT,
+                        value: "x".to_owned(),
+                        span:  span(26, 27),
+                    }),
+                    span:       span(26, 27),
+                }],
+                alternative: vec![ast::Statement::Expr {
+                    token:      token::IDENT,
+                    expression: ast::Expression::Ident(ast::Identifier {
+                        token: token::IDENT,
+                        value: "y".to_owned(),
+                        span:  span(37, 38),
+                    }),
+                    span:       span(37, 38),
+                }],
+                span:        span(13, 40),
+            }
         };
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), 5);
 
-        for stmt in &program.statements {
+        let lines = [(2, 1), (3, 41), (4, 81), (5, 122), (6, 163)];
+        for (stmt, (line, base)) in program.statements.iter().zip(lines) {
             match stmt {
                 ast::Statement::Expr {
                     token: _,
                     expression,
-                } => assert_eq!(*expression, expected),
+                    span: _,
+                } => assert_eq!(*expression, expected(line, base)),
                 _ => panic!("Not a Expr Statement"),
             }
         }
     }
 
+    #[test]
+    fn test_else_if_expression() {
+        let input = "
+            if (a) { 1 } else if (b) { 2 } else { 3 }
+            if a { 1 } else if b < c { 2 } else if c { 3 }
+            if (a) { 1 } else { if (b) { 2 } }
+            while x > 0 { x -= 1; }
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "if(a) { 1 } else if(b) { 2 } else { 3 }",
+            "if(a) { 1 } else if((b < c)) { 2 } else if(c) { 3 }",
+            "if(a) { 1 } else if(b) { 2 }",
+            "while((x > 0)) { (x -= 1) }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+    }
+
     #[test]
     fn test_function_expression() {
         let input = "
@@ -517,21 +913,36 @@ mod parser_tests {
         "
         .to_string();
 
-        let expected = vec![
+        let expected = [
             ast::Expression::FunctionLiteral {
                 token:      token::FUNC,
                 parameters: vec![
-                    Identifier {
-                        token: token::IDENT,
-                        value: "x".to_owned(),
+                    Parameter {
+                        pattern: Pattern::Ident(Identifier {
+                            token: token::IDENT,
+                            value: "x".to_owned(),
+                            span:  Span::new(2, 16, 16, 17),
+                        }),
+                        default: None,
+                        rest:    false,
                     },
-                    Identifier {
-                        token: token::IDENT,
-                        value: "y".to_owned(),
+                    Parameter {
+                        pattern: Pattern::Ident(Identifier {
+                            token: token::IDENT,
+                            value: "y".to_owned(),
+                            span:  Span::new(2, 19, 19, 20),
+                        }),
+                        default: None,
+                        rest:    false,
                     },
-                    Identifier {
-                        token: token::IDENT,
-                        value: "z".to_owned(),
+                    Parameter {
+                        pattern: Pattern::Ident(Identifier {
+                            token: token::IDENT,
+                            value: "z".to_owned(),
+                            span:  Span::new(2, 22, 22, 23),
+                        }),
+                        default: None,
+                        rest:    false,
                     },
                 ],
                 body:       vec![ast::Statement::Return {
@@ -543,20 +954,27 @@ mod parser_tests {
                             left:     Box::new(ast::Expression::Ident(ast::Identifier {
                                 token: token::IDENT,
                                 value: "x".to_owned(),
+                                span:  Span::new(2, 34, 34, 35),
                             })),
                             operator: "+".to_owned(),
                             right:    Box::new(ast::Expression::Ident(ast::Identifier {
                                 token: token::IDENT,
                                 value: "y".to_owned(),
+                                span:  Span::new(2, 38, 38, 39),
                             })),
+                            span:     Span::new(2, 34, 34, 39),
                         }),
                         operator: "+".to_owned(),
                         right:    Box::new(ast::Expression::Ident(ast::Identifier {
                             token: token::IDENT,
                             value: "z".to_owned(),
+                            span:  Span::new(2, 42, 42, 43),
                         })),
+                        span:     Span::new(2, 34, 34, 43),
                     },
+                    span:  Span::new(2, 27, 27, 44),
                 }],
+                span:       Span::new(2, 13, 13, 46),
             },
             ast::Expression::FunctionLiteral {
                 token:      token::FUNC,
@@ -566,26 +984,37 @@ mod parser_tests {
                     value: ast::Expression::IntegerLiteral {
                         token: token::INT,
                         value: 0,
+                        span:  Span::new(3, 27, 74, 75),
                     },
+                    span:  Span::new(3, 20, 67, 76),
                 }],
+                span:       Span::new(3, 13, 60, 78),
             },
             ast::Expression::FunctionLiteral {
                 token:      token::FUNC,
-                parameters: vec![Identifier {
-                    token: token::IDENT,
-                    value: "x".to_owned(),
+                parameters: vec![Parameter {
+                    pattern: Pattern::Ident(Identifier {
+                        token: token::IDENT,
+                        value: "x".to_owned(),
+                        span:  Span::new(4, 16, 95, 96),
+                    }),
+                    default: None,
+                    rest:    false,
                 }],
                 body:       vec![ast::Statement::Return {
                     token: token::RETURN,
                     value: ast::Expression::Ident(ast::Identifier {
                         token: token::IDENT,
                         value: "x".to_owned(),
+                        span:  Span::new(4, 28, 107, 108),
                     }),
+                    span:  Span::new(4, 21, 100, 109),
                 }],
+                span:       Span::new(4, 13, 92, 111),
             },
         ];
 
-        let program = Parser::parse(input);
+        let program = Parser::parse(input).unwrap();
 
         assert_eq!(program.statements.len(), expected.len());
 
@@ -594,9 +1023,629 @@ mod parser_tests {
                 ast::Statement::Expr {
                     token: _,
                     expression,
+                    span: _,
                 } => assert_eq!(*expression, expected[i]),
                 _ => panic!("Not a Expr Statement"),
             }
         }
     }
+
+    #[test]
+    fn test_function_statement() {
+        let input = "
+            fn add(x, y) { x + y }
+            fn noop() { };
+            fn(x) { x }(1);
+            fn swap(a, b) { let t = a; a = b; [a, t] }
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "fn add(x, y) { (x + y) }",
+            "fn noop() {  }",
+            "fn(x) { x }(1)",
+            "fn swap(a, b) { let t = a; (a = b) [a, t] }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+        match &program.statements[0] {
+            ast::Statement::Function {
+                name, parameters, ..
+            } => {
+                assert_eq!(name.value, "add");
+                assert_eq!(parameters.len(), 2);
+            }
+            _ => panic!("Not a Function Statement"),
+        }
+    }
+
+    #[test]
+    fn test_arrow_function() {
+        let input = "
+            x => x + 1;
+            (x, y) => x * y;
+            () => 42;
+            (x) => { let y = x; y };
+            (a + b) * c;
+            (a);
+            f(x => x, (a, b) => a)(1);
+            x => y => x + y;
+            let g = () => ({1: 2});
+            a = b => b;
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "fn(x) { (x + 1) }",
+            "fn(x, y) { (x * y) }",
+            "fn() { 42 }",
+            "fn(x) { let y = x; y }",
+            "((a + b) * c)",
+            "a",
+            "f(fn(x) { x }, fn(a, b) { a })(1)",
+            "fn(x) { fn(y) { (x + y) } }",
+            "let g = fn() { {1: 2} };",
+            "(a = fn(b) { b })",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            (a, 1) => a;
+            (a + b) => a;
+            (a, b);
+            ();
+        "
+        .to_string();
+
+        assert_parse_errors(
+            input,
+            &[
+                ("PAR0003", 2, 17),
+                ("PAR0003", 3, 16),
+                ("PAR0003", 4, 15),
+                ("PAR0004", 5, 14),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_parameters() {
+        let input = "
+            fn(x, y = 10) { x + y };
+            fn sum(first, ...rest) { first };
+            (x, y = x * 2, ...more) => y;
+            (...all) => all;
+            f(1, y: 2, z: a + b);
+            f(x: 1);
+            const c = 1;
+            let g = (c = 2) => c;
+            (c = 2, d) => c + d;
+            ([a, b] = [1, 2]) => a + b;
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "fn(x, y = 10) { (x + y) }",
+            "fn sum(first, ...rest) { first }",
+            "fn(x, y = (x * 2), ...more) { y }",
+            "fn(...all) { all }",
+            "f(1, y: 2, z: (a + b))",
+            "f(x: 1)",
+            "const c = 1;",
+            "let g = fn(c = 2) { c };",
+            "fn(c = 2, d) { (c + d) }",
+            "fn([a, b] = [1, 2]) { (a + b) }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            fn(...rest, x) { x };
+            fn(x, x) { x };
+            fn(...rest = 1) { rest };
+            (...rest, x) => x;
+            f(x: 1, 2);
+            f(x: 1, x: 2);
+            const k = 1; (k = 2);
+        "
+        .to_string();
+
+        assert_parse_errors(
+            input,
+            &[
+                ("PAR0013", 2, 19),
+                ("PAR0013", 3, 19),
+                ("PAR0013", 4, 24),
+                ("PAR0013", 5, 17),
+                ("PAR0014", 6, 21),
+                ("PAR0014", 7, 21),
+                ("PAR0012", 8, 29),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_destructuring() {
+        let input = "
+            let [a, b, ...rest] = xs;
+            const {name, age: years} = person;
+            let [first, [x, y], {\"full name\": full}] = nested;
+            let [] = [];
+            fn f([a, b], {k} = h, ...more) { a };
+            ([a, b], {k: v}) => a + v;
+            ({name}) => name;
+            ([x, {y}]) => x + y;
+            ({a, b}) => a + b;
+            (a, {b, c}) => b + c;
+            ({name, age: years}) => years;
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "let [a, b, ...rest] = xs;",
+            "const {name, age: years} = person;",
+            "let [first, [x, y], {full name: full}] = nested;",
+            "let [] = [];",
+            "fn f([a, b], {k} = h, ...more) { a }",
+            "fn([a, b], {k: v}) { (a + v) }",
+            "fn({name}) { name }",
+            "fn([x, {y}]) { (x + y) }",
+            "fn({a, b}) { (a + b) }",
+            "fn(a, {b, c}) { (b + c) }",
+            "fn({name, age: years}) { years }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            let [a, a] = xs;
+            let [...rest, b] = xs;
+            fn([a], a) { a };
+            ([a + 1]) => a;
+            let 1 = 1;
+        "
+        .to_string();
+
+        assert_parse_errors(
+            input,
+            &[
+                ("PAR0015", 2, 21),
+                ("PAR0003", 3, 25),
+                ("PAR0013", 4, 21),
+                ("PAR0003", 5, 17),
+                ("PAR0001", 6, 17),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_match_expression() {
+        let input = "
+            match (x) { 0 => \"zero\", -1 => \"minus one\", _ => \"other\" };
+            match x { [a, b] => a + b, {kind: \"a\", size} => size, n if n > 0 => n };
+            match (p) { [a, ...rest] if a == 1 => { let y = a; y } _ => 0 };
+            match f(x) { true => x => x, \"s\" => 1.5 };
+            match x { };
+            match x { n if (n > 0) => n };
+            match (v) { x if (any(xs, y => y == x)) => x, x if all((y) => y, fn(y) { y => y }) => 0 };
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "match(x) { 0 => { \"zero\" }, -1 => { \"minus one\" }, _ => { \"other\" } }",
+            "match(x) { [a, b] => { (a + b) }, {kind: \"a\", size} => { size }, n if (n > 0) => { n } }",
+            "match(p) { [a, ...rest] if (a == 1) => { let y = a; y }, _ => { 0 } }",
+            "match(f(x)) { true => { fn(x) { x } }, \"s\" => { 1.5 } }",
+            "match(x) {  }",
+            "match(x) { n if (n > 0) => { n } }",
+            "match(v) { x if any(xs, fn(y) { (y == x) }) => { x }, x if all(fn(y) { y }, fn(y) { fn(y) { y } }) => { 0 } }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            match x { 1 + 2 => 3 };
+            match x { [a, a] => a };
+            match x { 1 => 2 3 => 4 };
+            match x { a b };
+        "
+        .to_string();
+
+        assert_parse_errors(
+            input,
+            &[
+                ("PAR0003", 2, 25),
+                ("PAR0015", 3, 27),
+                ("PAR0003", 4, 30),
+                ("PAR0003", 5, 25),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_try_expression() {
+        let input = "
+            throw \"boom\";
+            try { risky(); } catch (e) { e[\"message\"] }
+            try { 1 } catch { 2 } finally { done() }
+            try { f() } finally { g() }
+            let v = try { 1 / x } catch ({message, code}) { code };
+            fn f() { throw {\"kind\": 1} }
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "throw \"boom\";",
+            "try { risky() } catch (e) { (e[\"message\"]) }",
+            "try { 1 } catch { 2 } finally { done() }",
+            "try { f() } finally { g() }",
+            "let v = try { (1 / x) } catch ({message, code}) { code };",
+            "fn f() { throw {\"kind\": 1}; }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            try { 1 };
+            try { 1 } catch e { 2 };
+            try { 1 } catch ([a, a]) { a };
+            throw;
+        "
+        .to_string();
+
+        let errors = assert_parse_errors(
+            input,
+            &[
+                ("PAR0003", 2, 22),
+                ("PAR0003", 3, 29),
+                ("PAR0015", 4, 34),
+                ("PAR0004", 5, 18),
+            ],
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "PAR0003: Expected \"catch\" or \"finally\", but found \";\" (line 2, column 22)"
+        );
+    }
+
+    #[test]
+    fn test_defer_statement() {
+        let input = "
+            fn f() { defer close(file); defer log(\"done\") }
+            let g = x => { while (x) { defer x = false; } };
+            fn h(xs) { for (let i = 0; i < 3; i += 1) { defer xs[i] = i } }
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "fn f() { defer close(file); defer log(\"done\"); }",
+            "let g = fn(x) { while(x) { defer (x = false); } };",
+            "fn h(xs) { for(let i = 0; (i < 3); (i += 1)) { defer ((xs[i]) = i); } }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let input = "
+            defer close(file);
+            fn f() { defer; }
+            if (x) { defer f(); }
+        "
+        .to_string();
+
+        let errors = assert_parse_errors(
+            input,
+            &[("PAR0016", 2, 13), ("PAR0004", 3, 27), ("PAR0016", 4, 22)],
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "PAR0016: 'defer' outside of a function (line 2, column 13)"
+        );
+    }
+
+    #[test]
+    fn test_loop_statement() {
+        let input = "
+            while (i < 10) { i = i + 1; };
+            for (let i = 0; i < 10; i = i + 1) { continue; };
+            for (;;) { break; }
+            outer: while (true) {
+                inner: for (i; ;) { break outer; continue inner; }
+            }
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "while((i < 10)) { (i = (i + 1)) }",
+            "for(let i = 0; (i < 10); (i = (i + 1))) { continue; }",
+            "for(; ; ) { break; }",
+            "outer: while(true) { inner: for(i; ; ) { break outer; continue inner; } }",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+
+        let label = &program.statements[3];
+        assert_eq!(label.span().line, 5);
+        assert_eq!(label.span().column, 13);
+    }
+
+    #[test]
+    fn test_loop_errors() {
+        let input = "
+            break;
+            while (true) { fn() { continue; }; }
+            outer: while (true) { break inner; }
+            outer: let x = 1;
+            for (let i = 0 i < 10) { }
+        "
+        .to_string();
+
+        assert_parse_errors(
+            input,
+            &[
+                ("PAR0008", 2, 13),
+                ("PAR0008", 3, 35),
+                ("PAR0009", 4, 41),
+                ("PAR0010", 5, 20),
+                ("PAR0003", 6, 28),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_assign_target() {
+        let input = "
+            1 = 2;
+            f() += 1;
+            a + b = c;
+            [a][0] = 1;
+        "
+        .to_string();
+
+        let errors = assert_parse_errors(
+            input,
+            &[
+                ("PAR0011", 2, 15),
+                ("PAR0011", 3, 17),
+                ("PAR0011", 4, 19),
+                ("PAR0011", 5, 20),
+            ],
+        );
+        assert_eq!(
+            errors[2].to_string(),
+            "PAR0011: Cannot assign to (a + b) (line 4, column 19)"
+        );
+    }
+
+    #[test]
+    fn test_block_expression() {
+        let input = "
+            { let x = 1; x + 1 };
+            { x };
+            { x; y };
+            { f(1) };
+            {x: 1};
+            { while (x) { break; } };
+            let y = { 1 } + 2;
+            { const z = 1; fn g() { z } g() };
+            { outer: while (true) { break outer; } 1 };
+            {k: while_x};
+        "
+        .to_string();
+
+        let expected: Vec<&str> = vec![
+            "{ let x = 1; (x + 1) }",
+            "{ x }",
+            "{ x y }",
+            "{ f(1) }",
+            "{x: 1}",
+            "{ while(x) { break; } }",
+            "let y = ({ 1 } + 2);",
+            "{ const z = 1; fn g() { z } g() }",
+            "{ outer: while(true) { break outer; } 1 }",
+            "{k: while_x}",
+        ];
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), expected.len());
+
+        for (i, exp) in expected.iter().enumerate() {
+            assert_eq!(program.statements[i].to_string(), *exp);
+        }
+    }
+
+    #[test]
+    fn test_const_statement() {
+        let input = "
+            const x = 1;
+            x = 2;
+            { let x = 3; x = 4; }
+            let f = fn(x) { x += 1; };
+            let g = fn() { x *= 2; };
+            const xs = [1];
+            xs[0] = 2;
+            for (const i = 0; i < 3; i += 1) { }
+            i = 1;
+        "
+        .to_string();
+
+        let errors = assert_parse_errors(
+            input,
+            &[
+                ("PAR0012", 3, 15),
+                ("PAR0012", 6, 30),
+                ("PAR0012", 8, 19),
+                ("PAR0012", 9, 40),
+            ],
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "PAR0012: Cannot assign to const 'x' (line 3, column 15)"
+        );
+
+        let program = Parser::parse("const y = 1 + 2".to_string()).unwrap();
+        assert_eq!(program.statements[0].to_string(), "const y = (1 + 2);");
+    }
+
+    #[test]
+    fn test_span() {
+        let input = "
+let add = fn(x, y) {
+    x + y
+};
+add(1, 2);
+"
+        .to_string();
+
+        let program = Parser::parse(input).unwrap();
+
+        assert_eq!(program.statements.len(), 2);
+
+        let span = program.statements[0].span();
+        assert_eq!(
+            (span.line, span.column, span.start, span.end),
+            (2, 1, 1, 34)
+        );
+
+        match &program.statements[0] {
+            ast::Statement::Let { value, .. } => {
+                let span = value.span();
+                assert_eq!(
+                    (span.line, span.column, span.start, span.end),
+                    (2, 11, 11, 33)
+                );
+
+                match value {
+                    ast::Expression::FunctionLiteral { body, .. } => {
+                        let span = body[0].span();
+                        assert_eq!(
+                            (span.line, span.column, span.start, span.end),
+                            (3, 5, 26, 31)
+                        );
+                    }
+                    _ => panic!("Not a Expression::FunctionLiteral"),
+                }
+            }
+            _ => panic!("Not a Let Statement"),
+        }
+
+        match &program.statements[1] {
+            ast::Statement::Expr { expression, .. } => {
+                let span = expression.span();
+                assert_eq!(
+                    (span.line, span.column, span.start, span.end),
+                    (5, 1, 35, 44)
+                );
+            }
+            _ => panic!("Not a Expr Statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "
+            let = 5;
+            let x 10;
+            let f = fn(x, 1) { x };
+            let g = fn(x) {
+                x +;
+                let y = ;
+                x
+            };
+            add(1, 2;
+            let ok = 1;
+        "
+        .to_string();
+
+        let errors = assert_parse_errors(
+            input,
+            &[
+                ("PAR0001", 2, 17),
+                ("PAR0002", 3, 19),
+                ("PAR0003", 4, 27),
+                ("PAR0004", 6, 20),
+                ("PAR0004", 7, 25),
+                ("PAR0003", 10, 21),
+            ],
+        );
+
+        assert_eq!(errors[1].expected, Some(token::ASSIGN));
+        assert_eq!(errors[1].found, token::INT);
+        assert_eq!(
+            errors[5].to_string(),
+            "PAR0003: Expected \")\", but found \";\" (line 10, column 21)"
+        );
+    }
+
+    #[test]
+    fn test_unclosed_block() {
+        let errors = match Parser::parse("if (x) { x".to_string()) {
+            Ok(_) => panic!("Not a parse error"),
+            Err(errors) => errors,
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "PAR0003");
+        assert_eq!(errors[0].expected, Some(token::RBRACE));
+        assert_eq!(errors[0].found, token::EOF);
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        let errors = assert_parse_errors("let s = \"abc".to_string(), &[("PAR0005", 1, 9)]);
+
+        assert_eq!(
+            errors[0].to_string(),
+            "PAR0005: Illegal token \"\\\"abc\" (line 1, column 9)"
+        );
+    }
 }
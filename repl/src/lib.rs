@@ -10,7 +10,7 @@ fn prompt(name: &str) -> String {
         .read_line(&mut line)
         .expect("Error REP0001: Fail to read a line");
 
-    return line.trim().to_string();
+    line.trim().to_string()
 }
 
 pub fn repl() {
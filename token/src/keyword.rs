@@ -2,34 +2,75 @@ pub type TokenType = &'static str;
 
 pub const ILLEGAL: TokenType = "ILLEGAL";
 pub const EOF: TokenType = "EOF";
+pub const COMMENT: TokenType = "COMMENT";
 
 pub const IDENT: TokenType = "IDENT";
 pub const INT: TokenType = "INT";
+pub const FLOAT: TokenType = "FLOAT";
+pub const STRING: TokenType = "STRING";
 
 pub const ASSIGN: TokenType = "=";
 pub const PLUS: TokenType = "+";
 pub const MINUS: TokenType = "-";
 pub const ASTERISK: TokenType = "*";
 pub const SLASH: TokenType = "/";
+pub const PERCENT: TokenType = "%";
+pub const POWER: TokenType = "**";
 pub const BANG: TokenType = "!";
 
+pub const PLUS_ASSIGN: TokenType = "+=";
+pub const MINUS_ASSIGN: TokenType = "-=";
+pub const ASTERISK_ASSIGN: TokenType = "*=";
+pub const SLASH_ASSIGN: TokenType = "/=";
+pub const PERCENT_ASSIGN: TokenType = "%=";
+
 pub const LT: TokenType = "<";
 pub const GT: TokenType = ">";
+pub const LTE: TokenType = "<=";
+pub const GTE: TokenType = ">=";
 pub const EQ: TokenType = "==";
 pub const NEQ: TokenType = "!=";
 
+pub const AND: TokenType = "&&";
+pub const OR: TokenType = "||";
+
+pub const BIT_AND: TokenType = "&";
+pub const BIT_OR: TokenType = "|";
+pub const BIT_XOR: TokenType = "^";
+pub const BIT_NOT: TokenType = "~";
+pub const SHL: TokenType = "<<";
+pub const SHR: TokenType = ">>";
+
+pub const ARROW: TokenType = "=>";
+pub const ELLIPSIS: TokenType = "...";
+pub const QUESTION: TokenType = "?";
+
 pub const COMMA: TokenType = ",";
 pub const SEMICOLON: TokenType = ";";
+pub const COLON: TokenType = ":";
 
 pub const LPAREN: TokenType = "(";
 pub const RPAREN: TokenType = ")";
 pub const LBRACE: TokenType = "{";
 pub const RBRACE: TokenType = "}";
+pub const LBRACKET: TokenType = "[";
+pub const RBRACKET: TokenType = "]";
 
 pub const LET: TokenType = "LET";
+pub const CONST: TokenType = "CONST";
 pub const FUNC: TokenType = "FUNCTION";
 pub const TRUE: TokenType = "TRUE";
 pub const FALSE: TokenType = "FALSE";
 pub const IF: TokenType = "IF";
 pub const ELSE: TokenType = "ELSE";
+pub const MATCH: TokenType = "MATCH";
 pub const RETURN: TokenType = "RETURN";
+pub const WHILE: TokenType = "WHILE";
+pub const FOR: TokenType = "FOR";
+pub const BREAK: TokenType = "BREAK";
+pub const CONTINUE: TokenType = "CONTINUE";
+pub const THROW: TokenType = "THROW";
+pub const TRY: TokenType = "TRY";
+pub const CATCH: TokenType = "CATCH";
+pub const FINALLY: TokenType = "FINALLY";
+pub const DEFER: TokenType = "DEFER";
@@ -1,13 +1,16 @@
 mod keyword;
+mod span;
 mod util;
 
 pub use keyword::*;
+pub use span::*;
 pub use util::*;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal:    String,
+    pub span:       Span,
 }
 
 impl Token {
@@ -15,6 +18,7 @@ impl Token {
         Self {
             token_type,
             literal,
+            span: Span::default(),
         }
     }
 }
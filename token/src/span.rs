@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter};
+
+// line and column are 1-based and count chars, start and end are byte offsets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub line:   usize,
+    pub column: usize,
+    pub start:  usize,
+    pub end:    usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, start: usize, end: usize) -> Self {
+        Self {
+            line,
+            column,
+            start,
+            end,
+        }
+    }
+
+    // span from the beginning of self to the end of other
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            line:   self.line,
+            column: self.column,
+            start:  self.start,
+            end:    other.end.max(self.end),
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
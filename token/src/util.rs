@@ -3,12 +3,23 @@ use super::keyword::*;
 pub fn look_up_ident(s: &str) -> TokenType {
     match s {
         "let" => LET,
+        "const" => CONST,
         "fn" => FUNC,
         "true" => TRUE,
         "false" => FALSE,
         "if" => IF,
         "else" => ELSE,
+        "match" => MATCH,
         "return" => RETURN,
+        "while" => WHILE,
+        "for" => FOR,
+        "break" => BREAK,
+        "continue" => CONTINUE,
+        "throw" => THROW,
+        "try" => TRY,
+        "catch" => CATCH,
+        "finally" => FINALLY,
+        "defer" => DEFER,
         _ => IDENT,
     }
 }